@@ -4,11 +4,15 @@ use std::io::Write;
 
 fn main() {
     let specs = [
-        //        "k8s_openapi::api::core::v1::NamespaceSpec",
+        OpenapiResource::new("k8s_openapi::api::core::v1::Namespace"),
         OpenapiResource::new("k8s_openapi::api::core::v1::Node"),
         OpenapiResource::new("k8s_openapi::api::core::v1::Pod"),
         //        "k8s_openapi::api::core::v1::ReplicationControllerSpec",
         OpenapiResource::new("k8s_openapi::api::core::v1::Service"),
+        OpenapiResource::new("k8s_openapi::api::core::v1::Secret"),
+        OpenapiResource::new("k8s_openapi::api::core::v1::ConfigMap"),
+        OpenapiResource::new("k8s_openapi::api::core::v1::LimitRange"),
+        OpenapiResource::new("k8s_openapi::api::core::v1::ResourceQuota"),
         OpenapiResource::new("k8s_openapi::api::apps::v1::DaemonSet"),
         OpenapiResource::new("k8s_openapi::api::apps::v1::Deployment"),
         OpenapiResource::new("k8s_openapi::api::apps::v1::ReplicaSet"),
@@ -18,9 +22,13 @@ fn main() {
         OpenapiResource::new("k8s_openapi::api::autoscaling::v2beta1::HorizontalPodAutoscaler"),
         OpenapiResource::new("k8s_openapi::api::autoscaling::v2beta2::HorizontalPodAutoscaler"),
         OpenapiResource::new("k8s_openapi::api::networking::v1beta1::Ingress"),
+        OpenapiResource::new("k8s_openapi::api::networking::v1::NetworkPolicy"),
         OpenapiResource::new("k8s_openapi::api::extensions::v1beta1::Ingress"),
         OpenapiResource::new("k8s_openapi::api::rbac::v1::ClusterRole"),
         OpenapiResource::new("k8s_openapi::api::rbac::v1::Role"),
+        OpenapiResource::new("k8s_openapi::api::rbac::v1::ClusterRoleBinding"),
+        OpenapiResource::new("k8s_openapi::api::rbac::v1::RoleBinding"),
+        OpenapiResource::new("k8s_openapi::api::storage::v1::StorageClass"),
     ];
 
     let lint = build_lint_trait(&specs);
@@ -44,7 +52,7 @@ fn main() {
     write_to(&source.trim(), "../korrecte-lib/src/linters/lint.rs");
     write_to(
         &build_kube_client(&specs),
-        "../korrecte-lib/src/kube/api_async.rs",
+        "../korrecte-lib/src/kube/repository/api_async.rs",
     )
 }
 
@@ -106,6 +114,10 @@ impl<'a> OpenapiResource<'a> {
             ty = &"rbac.authorization.k8s.io";
         }
 
+        if ty == &"storage" {
+            ty = &"storage.k8s.io";
+        }
+
         (ty, object, version)
     }
 }
@@ -134,13 +146,23 @@ use std::pin::Pin;
 use kube::api::{{Resource, ListParams, Meta}};
 use kube::runtime::Reflector;
 use futures::FutureExt;
+use k8s_openapi::apimachinery::pkg::version::Info;
+use k8s_openapi::http;
+use log::*;
 use serde::de::DeserializeOwned;
 
 pub struct ApiObjectRepository {{
     kubeclient: kube::client::APIClient,
 }}
 
-type ReflectorFuture<'a> = Box<dyn Future<Output=Result<Vec<KubeObjectType>, anyhow::Error>> + 'a>;
+/// Objects loaded from the cluster. Kinds which could not be listed, like the ones the user has
+/// no access to, are missing from `objects` and listed on `failed_kinds`.
+pub struct ClusterObjects {{
+    pub objects: Vec<KubeObjectType>,
+    pub failed_kinds: Vec<String>,
+}}
+
+type ReflectorFuture<'a> = Box<dyn Future<Output=(&'static str, Result<Vec<KubeObjectType>, anyhow::Error>)> + 'a>;
 
 impl ApiObjectRepository {{
     pub fn new() -> Result<Self> {{
@@ -149,26 +171,45 @@ impl ApiObjectRepository {{
         Ok(Self {{ kubeclient }})
     }}
 
-    pub async fn load_all_objects(&self) -> Result<Vec<KubeObjectType>, ()> {{
+    pub async fn load_all_objects(&self) -> ClusterObjects {{
         let mut v: Vec<Pin<ReflectorFuture>> = Vec::new();
         let mut objects = Vec::new();
+        let mut failed_kinds = Vec::new();
 
         {}
 
-        let all_futures: Vec<Result<Vec<KubeObjectType>, anyhow::Error>> =
+        let all_futures: Vec<(&'static str, Result<Vec<KubeObjectType>, anyhow::Error>)> =
             futures::future::join_all(v).await;
 
-        for f in all_futures {{
+        for (ty, f) in all_futures {{
             match f {{
-                Err(ref e) => println!("Error loading some resource: {{}}", e),
+                Err(ref e) => {{
+                    warn!("{{}}", e);
+                    failed_kinds.push(ty.to_string());
+                }}
                 Ok(current) => objects.extend(current),
             }}
         }}
 
-        Ok(objects)
+        ClusterObjects {{ objects, failed_kinds }}
     }}
 
-    pub async fn reflector_for<R: ReflectorFor>(&self, ty: &'static str) -> Result<Vec<KubeObjectType>, anyhow::Error> {{
+    /// Version of the API server
+    pub async fn server_version(&self) -> Result<Info> {{
+        let request = http::Request::get("/version").body(Vec::new())?;
+
+        Ok(self.kubeclient.request::<Info>(request).await?)
+    }}
+
+    pub async fn reflector_for<R: ReflectorFor>(&self, ty: &'static str) -> (&'static str, Result<Vec<KubeObjectType>, anyhow::Error>) {{
+        let objects = self.list::<R>()
+            .await
+            .map_err(|e| anyhow!("Err loading {{}}: {{}}", ty, e));
+
+        (ty, objects)
+    }}
+
+    async fn list<R: ReflectorFor>(&self) -> Result<Vec<KubeObjectType>, anyhow::Error> {{
         let client = self.kubeclient.clone();
 
         let reflector = Reflector::<R>::new(
@@ -178,15 +219,12 @@ impl ApiObjectRepository {{
         );
         let reflector = reflector.init().await?;
 
-        reflector
+        Ok(reflector
             .state()
-            .await
-            .map(|objects| {{
-                objects.iter()
-                    .map(|obj| obj.clone().into())
-                    .collect()
-            }})
-            .map_err(|e| anyhow!("Err loading {{}}: {{}}", ty, e))
+            .await?
+            .iter()
+            .map(|obj| obj.clone().into())
+            .collect())
     }}
 }}
 
@@ -200,10 +238,10 @@ pub struct FrozenObjectRepository {{
 impl From<ApiObjectRepository> for FrozenObjectRepository {{
     fn from(api: ApiObjectRepository) -> Self {{
         let mut rt = tokio::runtime::Runtime::new().unwrap();
-        let all_objects = rt.block_on(api.load_all_objects()).unwrap();
+        let all_objects = rt.block_on(api.load_all_objects());
 
         FrozenObjectRepository {{
-            objects: all_objects,
+            objects: all_objects.objects,
         }}
     }}
 }}
@@ -289,6 +327,8 @@ fn build_enum(specs: &[OpenapiResource]) -> String {
     let mut match_arms = Vec::new();
     let mut types_arms = Vec::new();
     let mut metadata_arms = Vec::new();
+    let mut kind_arms = Vec::new();
+    let mut json_arms = Vec::new();
 
     for s in specs {
         let ty = s.fqn();
@@ -329,6 +369,23 @@ fn build_enum(specs: &[OpenapiResource]) -> String {
             s.variant(),
         );
         metadata_arms.push(metadata_arm_str);
+
+        let kind_arm_str = format!(
+            r##"
+            KubeObjectType::{}(_) => "{}",
+            "##,
+            s.variant(),
+            parts.1,
+        );
+        kind_arms.push(kind_arm_str);
+
+        let json_arm_str = format!(
+            r##"
+            KubeObjectType::{}(ref o) => serde_json::to_value(o),
+            "##,
+            s.variant(),
+        );
+        json_arms.push(json_arm_str);
     }
 
     format!("
@@ -370,8 +427,20 @@ impl KubeObjectType {{
             {}
         }}
     }}
+
+    pub fn kind(&self) -> &'static str {{
+        match *self {{
+            {}
+        }}
+    }}
+
+    pub fn to_json(&self) -> Result<serde_json::Value, serde_json::Error> {{
+        match *self {{
+            {}
+        }}
+    }}
 }}
-", variants, match_arms.join("\n"), types_arms.join("\n"), metadata_arms.join("\n"))
+", variants, match_arms.join("\n"), types_arms.join("\n"), metadata_arms.join("\n"), kind_arms.join("\n"), json_arms.join("\n"))
 }
 
 fn write_to(content: &str, path: &str) {
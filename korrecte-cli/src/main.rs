@@ -3,8 +3,11 @@ mod view;
 use anyhow::{anyhow, Result};
 use clap::load_yaml;
 use clap::{App, ArgMatches};
+use korrecte::config::{ConfigLayers, ObjectFilters};
 use korrecte::executor::{ExecutionContextBuilder, ExecutionMode, Executor};
 use korrecte::reporting::Reporter;
+use korrecte::snapshot::Snapshot;
+use std::fs;
 use std::path::Path;
 
 use crate::view::Cli;
@@ -14,23 +17,136 @@ fn main() -> Result<()> {
     let yaml = load_yaml!("../cli.yaml");
     let matches = App::from_yaml(yaml).get_matches();
 
+    if let Some(fix_matches) = matches.subcommand_matches("fix") {
+        return fix(&matches, fix_matches);
+    }
+    if let Some(snapshot_matches) = matches.subcommand_matches("snapshot") {
+        return snapshot(snapshot_matches);
+    }
+    if let Some(config_matches) = matches.subcommand_matches("config") {
+        return config(&matches, config_matches);
+    }
+
+    let mode = get_execution_mode(&matches).ok_or_else(|| anyhow!("Invalid execution mode"))?;
+    let manifests = match mode {
+        ExecutionMode::FileSystem(path) => path,
+        ExecutionMode::Api => Path::new("."),
+    };
     let ctx = ExecutionContextBuilder::default()
-        .configuration_from_path(&Path::new(
-            matches.value_of("config").unwrap_or("korrecte.toml"),
-        ))?
-        .execution_mode(
-            get_execution_mode(&matches).ok_or_else(|| anyhow!("Invalid execution mode"))?,
-        )
+        .configuration_from_layers(&config_layers(&matches, &matches, manifests)?)?
+        .execution_mode(mode)
         .build();
 
+    let lint_specs = ctx.lint_specs()?;
     let executor = Executor::with_context(ctx);
     let reporter = executor.execute()?;
 
-    Cli::render(&reporter.findings())?;
+    Cli::render(&reporter.findings(), &lint_specs)?;
+
+    Ok(())
+}
+
+fn fix(matches: &ArgMatches, fix_matches: &ArgMatches) -> Result<()> {
+    let path = Path::new(fix_matches.value_of("path").unwrap_or_default());
+    let dry_run = fix_matches.is_present("dry-run");
+    let allow_unsafe = fix_matches.is_present("unsafe");
+
+    let ctx = ExecutionContextBuilder::default()
+        .configuration_from_layers(&config_layers(matches, fix_matches, path)?)?
+        .execution_mode(ExecutionMode::FileSystem(path))
+        .build();
+
+    let lint_specs = ctx.lint_specs()?;
+    let executor = Executor::with_context(ctx);
+
+    let mut failed = 0;
+    for file_fixes in executor.fix(allow_unsafe)? {
+        let file_fixes = match file_fixes {
+            Ok(file_fixes) => file_fixes,
+            Err(e) => {
+                eprintln!("{}", e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        Cli::render_fixes(&file_fixes, &lint_specs, dry_run)?;
+        if !dry_run && file_fixes.has_changes() {
+            fs::write(&file_fixes.path, &file_fixes.fixed)?;
+        }
+    }
+
+    if failed > 0 {
+        return Err(anyhow!("{} file(s) could not be fixed", failed));
+    }
+
+    Ok(())
+}
+
+fn snapshot(snapshot_matches: &ArgMatches) -> Result<()> {
+    let out = Path::new(snapshot_matches.value_of("out").unwrap_or_default());
+
+    let snapshot = Snapshot::capture()?;
+    snapshot.write(out)?;
+    Cli::render_snapshot(&snapshot, out);
+
+    let failed_kinds = &snapshot.metadata.failed_kinds;
+    if !failed_kinds.is_empty() {
+        return Err(anyhow!(
+            "The snapshot is incomplete, these kinds could not be captured: {}",
+            failed_kinds.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+fn config(matches: &ArgMatches, config_matches: &ArgMatches) -> Result<()> {
+    let manifests = Path::new(matches.value_of("path").unwrap_or("."));
+
+    if let Some(validate_matches) = config_matches.subcommand_matches("validate") {
+        let layers = config_layers(matches, validate_matches, manifests)?;
+        ExecutionContextBuilder::default().configuration_from_layers(&layers)?;
+        Cli::render_config_sources(&layers);
+    }
+    if let Some(show_matches) = config_matches.subcommand_matches("show") {
+        let layers = config_layers(matches, show_matches, manifests)?;
+        Cli::render_config(&layers, show_matches.is_present("effective"))?;
+    }
 
     Ok(())
 }
 
+/// Configuration layers of the execution. The command line filters are read from the matches of
+/// the subcommand, as they are global arguments.
+fn config_layers(
+    matches: &ArgMatches,
+    subcommand_matches: &ArgMatches,
+    manifests: &Path,
+) -> Result<ConfigLayers> {
+    let file = matches.value_of("config").map(Path::new);
+
+    Ok(ConfigLayers::load(file, manifests)?.object_filters(object_filters(subcommand_matches))?)
+}
+
+fn object_filters(matches: &ArgMatches) -> ObjectFilters {
+    let values = |name: &str| -> Option<Vec<String>> {
+        matches
+            .values_of(name)
+            .map(|values| values.map(String::from).collect())
+    };
+
+    ObjectFilters {
+        namespaces: values("namespace"),
+        ignored_namespaces: values("ignore-namespace"),
+        names: values("name"),
+        ignored_names: values("ignore-name"),
+        kinds: values("kind"),
+        ignored_kinds: values("ignore-kind"),
+        selector: matches.value_of("selector").map(String::from),
+    }
+}
+
 fn get_execution_mode<'a>(matches: &'a ArgMatches) -> Option<ExecutionMode<'a>> {
     match matches.value_of("source") {
         Some("api") | None => Some(ExecutionMode::Api),
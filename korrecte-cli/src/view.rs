@@ -1,22 +1,29 @@
 use anyhow::*;
 use colored::*;
+use korrecte::config::ConfigLayers;
+use korrecte::fix::FileFixes;
 use korrecte::linters::LintSpecLoader;
 use korrecte::reporting::Finding;
+use korrecte::snapshot::Snapshot;
+use similar::TextDiff;
+use std::path::Path;
+use toml::Value;
 
 pub struct Cli;
 
 impl Cli {
-    pub fn render(findings: &[Finding]) -> Result<()> {
-        let lint_specs = LintSpecLoader::new()?;
-
+    pub fn render(findings: &[Finding], lint_specs: &LintSpecLoader) -> Result<()> {
         for finding in findings {
             let spec = lint_specs
                 .get(finding.lint_name())
                 .ok_or_else(|| anyhow!("Missing spec for finding"))?;
 
+            let severity = finding.severity().unwrap_or(&spec.severity);
+
             println!(
-                "{} on {} [{}]. Metadata: {:?}",
+                "{} ({}) on {} [{}]. Metadata: {:?}",
                 spec.name.bold(),
+                severity,
                 finding.name().green(),
                 finding
                     .namespace()
@@ -24,9 +31,118 @@ impl Cli {
                     .unwrap_or(&"default".to_string())
                     .blue(),
                 finding.lint_metadata(),
-            )
+            );
+
+            if let Some(fix) = finding.fix() {
+                let kind = if finding.is_fix_unsafe() {
+                    "unsafe fix"
+                } else {
+                    "fix"
+                };
+                println!("  Suggested {}: {}", kind, fix);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn render_fixes(fixes: &FileFixes, lint_specs: &LintSpecLoader, diff: bool) -> Result<()> {
+        let path = fixes.path.display().to_string();
+
+        let statuses = [
+            (&fixes.applied, "Fixed"),
+            (&fixes.skipped, "Skipped"),
+            (&fixes.unsafe_skipped, "Skipped unsafe fix of"),
+        ];
+
+        for (findings, status) in statuses.iter() {
+            for finding in findings.iter() {
+                let spec = lint_specs
+                    .get(finding.lint_name())
+                    .ok_or_else(|| anyhow!("Missing spec for finding"))?;
+
+                println!(
+                    "{} {} on {} [{}] in {}",
+                    status.yellow(),
+                    spec.name.bold(),
+                    finding.name().green(),
+                    finding
+                        .namespace()
+                        .as_ref()
+                        .unwrap_or(&"default".to_string())
+                        .blue(),
+                    path,
+                );
+            }
+        }
+
+        if diff && fixes.has_changes() {
+            print!(
+                "{}",
+                TextDiff::from_lines(&fixes.original, &fixes.fixed)
+                    .unified_diff()
+                    .header(&path, &path)
+            );
         }
 
         Ok(())
     }
+
+    pub fn render_config_sources(layers: &ConfigLayers) {
+        if layers.layers().is_empty() {
+            println!("No configuration found: using the built-in defaults");
+        }
+
+        for layer in layers.layers() {
+            println!("{} is valid", layer.origin);
+        }
+    }
+
+    /// Prints the configuration as TOML. The effective configuration is the one resolved from all
+    /// the layers, including the built-in defaults of the keys which are not set.
+    pub fn render_config(layers: &ConfigLayers, effective: bool) -> Result<()> {
+        if effective {
+            if layers.layers().is_empty() {
+                println!("# No configuration found: using the built-in defaults");
+            }
+            for layer in layers.layers() {
+                println!("# Source: {}", layer.origin);
+            }
+            print!("{}", layers.config()?.to_toml()?);
+
+            return Ok(());
+        }
+
+        if layers.layers().is_empty() {
+            println!("# No configuration found: using the built-in defaults");
+            return Ok(());
+        }
+
+        for (index, layer) in layers.layers().iter().enumerate() {
+            if index > 0 {
+                println!();
+            }
+            println!("# {}", layer.origin);
+            print!("{}", toml::to_string(&Value::Table(layer.table.clone()))?);
+        }
+
+        Ok(())
+    }
+
+    pub fn render_snapshot(snapshot: &Snapshot, path: &Path) {
+        let metadata = &snapshot.metadata;
+        let server_version = metadata
+            .server_version
+            .as_ref()
+            .map(|version| version.git_version.as_str())
+            .unwrap_or("unknown version");
+
+        println!(
+            "Captured {} objects from Kubernetes {} at {} into {}",
+            metadata.objects.to_string().bold(),
+            server_version,
+            metadata.captured_at.0.to_rfc3339(),
+            path.display()
+        );
+    }
 }
@@ -0,0 +1,396 @@
+use super::{merge, Config, ConfigError, ObjectFilters, Problems};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::value::Table;
+use toml::Value;
+
+/// Directory of the system wide configuration file
+pub const SYSTEM_DIRECTORY: &str = "/etc/korrecte";
+
+/// Names of the configuration files, in order of preference when several live on the same
+/// directory
+const FILE_NAMES: [&str; 3] = ["korrecte.toml", "korrecte.yaml", "korrecte.yml"];
+
+/// Prefix of the environment variables which configure korrecte
+const ENV_PREFIX: &str = "KORRECTE_";
+/// Separator of the sections and keys on the name of the environment variables
+const ENV_SEPARATOR: &str = "__";
+
+/// Configuration read from several sources. Each layer is merged on top of the previous ones:
+/// tables are merged recursively and any other value, including arrays, is replaced. Keys which
+/// are not defined on any layer take their built-in defaults.
+#[derive(Default, Debug)]
+pub struct ConfigLayers {
+    layers: Vec<Layer>,
+}
+
+#[derive(Debug)]
+pub struct Layer {
+    /// Human readable description of where the layer comes from
+    pub origin: String,
+    /// Directory of the file the layer was read from. Relative paths defined on the layer are
+    /// resolved against it.
+    pub directory: Option<PathBuf>,
+    pub table: Table,
+}
+
+impl ConfigLayers {
+    /// Layers read on every execution: the system wide file, the given file or the closest one
+    /// to the manifests, and the environment variables
+    pub fn load(file: Option<&Path>, manifests: &Path) -> Result<Self, ConfigError> {
+        let layers = ConfigLayers::default().system(Path::new(SYSTEM_DIRECTORY))?;
+        let layers = match file {
+            Some(file) => layers.file(file)?,
+            None => layers.discover(manifests)?,
+        };
+
+        layers.environment(std::env::vars())
+    }
+
+    /// Adds the first configuration file found on the given directory, if any
+    pub fn system(self, directory: &Path) -> Result<Self, ConfigError> {
+        match config_file_in(directory) {
+            Some(path) => self.file(&path),
+            None => Ok(self),
+        }
+    }
+
+    /// Adds the given configuration file, which has to exist
+    pub fn file(self, path: &Path) -> Result<Self, ConfigError> {
+        if !path.exists() {
+            return Err(ConfigError::NotFound(path.to_path_buf()));
+        }
+
+        let origin = path.display().to_string();
+        let mut table = read_file(path).map_err(|e| e.on(&origin))?;
+        let directory = path.parent().map(Path::to_path_buf);
+        if let Some(ref directory) = directory {
+            resolve_paths(&mut table, directory);
+        }
+
+        self.layer(origin, directory, table)
+    }
+
+    /// Adds the closest configuration file found on the given path or any of its parents
+    pub fn discover(self, from: &Path) -> Result<Self, ConfigError> {
+        let start = match fs::canonicalize(from) {
+            Ok(start) if start.is_file() => start.parent().map(Path::to_path_buf),
+            Ok(start) => Some(start),
+            Err(_) => None,
+        };
+
+        let found = start.and_then(|start| start.ancestors().find_map(config_file_in));
+        match found {
+            Some(path) => self.file(&path),
+            None => Ok(self),
+        }
+    }
+
+    /// Adds the variables named like `KORRECTE_<SECTION>__<KEY>`, where nested keys are also
+    /// separated by `__`. Values are parsed as TOML values, falling back to plain strings.
+    ///
+    /// Variables without any separator are ignored, as Kubernetes defines variables like
+    /// `KORRECTE_SERVICE_HOST` for services named `korrecte`.
+    pub fn environment<I>(self, vars: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut table = Table::new();
+        for (name, value) in vars {
+            let path = match name.strip_prefix(ENV_PREFIX) {
+                Some(path) if path.contains(ENV_SEPARATOR) => path.to_lowercase(),
+                _ => continue,
+            };
+
+            let keys: Vec<&str> = path.split(ENV_SEPARATOR).collect();
+            if keys.iter().any(|key| key.is_empty()) {
+                let problem = format!("Invalid environment variable `{}`", name);
+                return Err(ConfigError::Invalid(Problems(vec![problem])).on("environment"));
+            }
+
+            merge(&mut table, &nested(&keys, env_value(&value)));
+        }
+
+        if table.is_empty() {
+            return Ok(self);
+        }
+
+        self.layer("environment".to_string(), None, table)
+    }
+
+    /// Adds the object filters given on the command line
+    pub fn object_filters(self, filters: ObjectFilters) -> Result<Self, ConfigError> {
+        let table = filters.into_table();
+        if table.is_empty() {
+            return Ok(self);
+        }
+
+        self.layer("command line".to_string(), None, table)
+    }
+
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    /// Result of merging all the layers
+    pub fn merged(&self) -> Table {
+        let mut merged = Table::new();
+        for layer in &self.layers {
+            merge(&mut merged, &layer.table);
+        }
+
+        merged
+    }
+
+    /// Validated configuration resulting of merging all the layers
+    pub fn config(&self) -> Result<Config, ConfigError> {
+        Config::from_source(self.merged())
+    }
+
+    /// Each layer is validated on its own, so problems can be traced to their origin
+    fn layer(
+        mut self,
+        origin: String,
+        directory: Option<PathBuf>,
+        table: Table,
+    ) -> Result<Self, ConfigError> {
+        Config::from_source(table.clone()).map_err(|e| e.on(&origin))?;
+        self.layers.push(Layer {
+            origin,
+            directory,
+            table,
+        });
+
+        Ok(self)
+    }
+}
+
+impl ObjectFilters {
+    fn into_table(self) -> Table {
+        let mut table = Table::new();
+        let lists = vec![
+            ("allowed_namespaces", self.namespaces),
+            ("ignored_namespaces", self.ignored_namespaces),
+            ("allowed_names", self.names),
+            ("ignored_names", self.ignored_names),
+            ("allowed_kinds", self.kinds),
+            ("ignored_kinds", self.ignored_kinds),
+        ];
+        for (key, values) in lists {
+            if let Some(values) = values {
+                let values = values.into_iter().map(Value::String).collect();
+                table.insert(key.to_string(), Value::Array(values));
+            }
+        }
+        if let Some(selector) = self.selector {
+            table.insert("selector".to_string(), Value::String(selector));
+        }
+
+        if table.is_empty() {
+            return table;
+        }
+        nested(&["korrecte"], Value::Table(table))
+    }
+}
+
+fn config_file_in(directory: &Path) -> Option<PathBuf> {
+    FILE_NAMES
+        .iter()
+        .map(|name| directory.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Reads a TOML or YAML file, depending on its extension
+fn read_file(path: &Path) -> Result<Table, ConfigError> {
+    let buffer = fs::read_to_string(path).map_err(|e| ConfigError::Read(path.to_path_buf(), e))?;
+
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("yaml") | Some("yml") if buffer.trim().is_empty() => Ok(Table::new()),
+        Some("yaml") | Some("yml") => Ok(serde_yaml::from_str(&buffer)?),
+        _ => Ok(toml::from_str(&buffer)?),
+    }
+}
+
+/// Makes the paths of the files loaded by the configuration (custom rules, scripts and plugins)
+/// relative to the given directory instead of the working directory. Absolute paths are kept.
+fn resolve_paths(table: &mut Table, directory: &Path) {
+    let resolve = |value: &mut Value| {
+        if let Value::String(path) = value {
+            *path = directory.join(&path).display().to_string();
+        }
+    };
+
+    if let Some(Value::Table(custom_rules)) = table.get_mut("custom_rules") {
+        if let Some(Value::Array(files)) = custom_rules.get_mut("files") {
+            files.iter_mut().for_each(resolve);
+        }
+    }
+
+    if let Some(Value::Table(scripts)) = table.get_mut("scripts") {
+        if let Some(Value::Array(lints)) = scripts.get_mut("lints") {
+            for lint in lints.iter_mut() {
+                if let Some(path) = lint.get_mut("path") {
+                    resolve(path);
+                }
+            }
+        }
+    }
+
+    if let Some(Value::Table(plugins)) = table.get_mut("plugins") {
+        if let Some(directory) = plugins.get_mut("directory") {
+            resolve(directory);
+        }
+    }
+}
+
+fn env_value(raw: &str) -> Value {
+    let parsed = toml::from_str::<Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut table| table.remove("value"));
+
+    parsed.unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+/// Table containing the value under the given path of keys
+fn nested(keys: &[&str], value: Value) -> Table {
+    let mut value = value;
+    for key in keys.iter().skip(1).rev() {
+        let mut table = Table::new();
+        table.insert(key.to_string(), value);
+        value = Value::Table(table);
+    }
+
+    let mut table = Table::new();
+    if let Some(first) = keys.first() {
+        table.insert(first.to_string(), value);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConfigLayers;
+    use crate::config::{ConfigError, ObjectFilters};
+    use std::path::Path;
+    use toml::Value;
+
+    fn var(name: &str, value: &str) -> (String, String) {
+        (name.to_string(), value.to_string())
+    }
+
+    #[test]
+    fn it_merges_the_layers_in_order() {
+        let layers = ConfigLayers::default()
+            .system(Path::new("../tests/config/system"))
+            .unwrap()
+            .discover(Path::new("../tests/config/project/manifests/app.yaml"))
+            .unwrap()
+            .environment(vec![
+                var("KORRECTE_HARDCODED_SECRETS__MIN_ENTROPY_LENGTH", "40"),
+                var("KORRECTE_SERVICE_HOST", "10.0.0.1"),
+                var("PATH", "/usr/bin"),
+            ])
+            .unwrap()
+            .object_filters(ObjectFilters {
+                kinds: Some(vec!["Deployment".to_string()]),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let origins: Vec<&str> = layers
+            .layers()
+            .iter()
+            .map(|layer| layer.origin.as_str())
+            .collect();
+        assert_eq!(4, origins.len());
+        assert!(origins[0].ends_with("system/korrecte.toml"));
+        assert!(origins[1].ends_with("project/korrecte.yaml"));
+        assert_eq!(&["environment", "command line"], &origins[2..]);
+
+        let merged = layers.merged();
+        let secrets = &merged["hardcoded_secrets"];
+        assert_eq!(Value::Float(3.5), secrets["entropy_threshold"]);
+        assert_eq!(Value::Integer(40), secrets["min_entropy_length"]);
+        assert_eq!(
+            Value::Array(vec![Value::String("team".to_string())]),
+            merged["required_labels"]["labels"]
+        );
+        assert_eq!(
+            Value::Array(vec![Value::String("Deployment".to_string())]),
+            merged["korrecte"]["allowed_kinds"]
+        );
+        assert_eq!(
+            Value::Array(vec![Value::String("kube-system".to_string())]),
+            merged["korrecte"]["ignored_namespaces"]
+        );
+        assert!(layers.config().is_ok());
+    }
+
+    #[test]
+    fn it_resolves_paths_against_the_directory_of_the_file() {
+        let layers = ConfigLayers::default()
+            .discover(Path::new(
+                "../tests/config/relative/deploy/manifests/app.yaml",
+            ))
+            .unwrap();
+        let directory = layers.layers()[0].directory.clone().unwrap();
+        assert!(directory.ends_with("relative/deploy"));
+
+        let mut config = layers.config().unwrap();
+        config.custom_rules.load_files().unwrap();
+        assert_eq!(1, config.custom_rules.rules().len());
+
+        let merged = layers.merged();
+        assert_eq!(
+            Value::String(
+                directory
+                    .join("scripts/service_without_pods.rhai")
+                    .display()
+                    .to_string()
+            ),
+            merged["scripts"]["lints"][0]["path"]
+        );
+        assert_eq!(
+            Value::String("/opt/korrecte/plugins".to_string()),
+            merged["plugins"]["directory"]
+        );
+    }
+
+    #[test]
+    fn it_parses_environment_values() {
+        let layers = ConfigLayers::default()
+            .environment(vec![
+                var(
+                    "KORRECTE_KORRECTE__IGNORED_NAMESPACES",
+                    "[\"kube-*\", \"monitoring\"]",
+                ),
+                var("KORRECTE_KORRECTE__SELECTOR", "app=web"),
+            ])
+            .unwrap();
+        let korrecte = &layers.merged()["korrecte"];
+
+        assert_eq!(
+            Value::Array(vec![
+                Value::String("kube-*".to_string()),
+                Value::String("monitoring".to_string())
+            ]),
+            korrecte["ignored_namespaces"]
+        );
+        assert_eq!(Value::String("app=web".to_string()), korrecte["selector"]);
+    }
+
+    #[test]
+    fn it_reports_the_origin_of_invalid_layers() {
+        let result = ConfigLayers::default()
+            .environment(vec![var("KORRECTE_REQUIRED_LABELS__LABLES", "[\"app\"]")]);
+
+        match result {
+            Err(e @ ConfigError::Layer { .. }) => assert_eq!(
+                "environment: Invalid configuration:\n  - Unknown key `lables` on section `required_labels`. Did you mean `labels`?",
+                e.to_string()
+            ),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+}
@@ -1,8 +1,72 @@
+use crate::linters::custom::Config as CustomRulesConfig;
+use crate::linters::evaluator::ObjectFilter;
 use crate::linters::lints::environment_passwords::Config as EnvironmentPasswordsConfig;
+use crate::linters::lints::hardcoded_secrets::Config as HardcodedSecretsConfig;
+use crate::linters::lints::pod_requirements::Config as PodRequirementsConfig;
 use crate::linters::lints::required_labels::Config as RequiredLabelsConfig;
-use serde::Deserialize;
+use crate::linters::plugins::Config as PluginsConfig;
+use crate::linters::scripting::Config as ScriptsConfig;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+use toml::value::Table;
+use toml::Value;
 
-#[derive(Default, Deserialize, Debug)]
+mod layers;
+mod overrides;
+mod registry;
+
+pub use layers::{ConfigLayers, Layer, SYSTEM_DIRECTORY};
+pub(crate) use overrides::Override;
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Configuration file {0} does not exist")]
+    NotFound(PathBuf),
+    #[error("Could not read configuration file {0}: {1}")]
+    Read(PathBuf, #[source] io::Error),
+    #[error("Could not parse configuration: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Could not parse configuration: {0}")]
+    ParseYaml(#[from] serde_yaml::Error),
+    #[error("Invalid configuration:\n{0}")]
+    Invalid(Problems),
+    #[error("{origin}: {source}")]
+    Layer {
+        origin: String,
+        source: Box<ConfigError>,
+    },
+}
+
+impl ConfigError {
+    /// Attaches the origin of the configuration which caused the error
+    fn on(self, origin: &str) -> ConfigError {
+        ConfigError::Layer {
+            origin: origin.to_string(),
+            source: Box::new(self),
+        }
+    }
+}
+
+/// Problems found while validating a configuration
+#[derive(Debug)]
+pub struct Problems(Vec<String>);
+
+impl fmt::Display for Problems {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self
+            .0
+            .iter()
+            .map(|problem| format!("  - {}", problem))
+            .collect();
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[derive(Default, Deserialize, Serialize, Debug)]
 pub struct Config {
     #[serde(default)]
     pub(crate) korrecte: KorrecteConfig,
@@ -12,10 +76,176 @@ pub struct Config {
 
     #[serde(default)]
     pub(crate) environment_passwords: EnvironmentPasswordsConfig,
+
+    #[serde(default)]
+    pub(crate) pod_requirements: PodRequirementsConfig,
+
+    #[serde(default)]
+    pub(crate) hardcoded_secrets: HardcodedSecretsConfig,
+
+    #[serde(default)]
+    pub(crate) custom_rules: CustomRulesConfig,
+
+    #[serde(default)]
+    pub(crate) scripts: ScriptsConfig,
+
+    #[serde(default)]
+    pub(crate) plugins: PluginsConfig,
+
+    #[serde(default)]
+    pub(crate) overrides: Vec<Override>,
+
+    /// Parsed source of the configuration, on top of which overrides are applied
+    #[serde(skip)]
+    pub(crate) source: Table,
 }
 
-#[derive(Default, Deserialize, Debug)]
+impl Config {
+    /// Parses a TOML configuration. Sections and keys which do not belong to any registered
+    /// configuration are rejected, instead of being silently ignored.
+    pub fn from_toml(source: &str) -> Result<Config, ConfigError> {
+        Self::from_source(toml::from_str(source)?)
+    }
+
+    /// Builds the configuration from an already parsed source, validating it
+    fn from_source(source: Table) -> Result<Config, ConfigError> {
+        let config = Self::from_table(source)?;
+
+        let mut problems = config.korrecte.filter_problems();
+        problems.extend(config.override_problems());
+        if !problems.is_empty() {
+            return Err(ConfigError::Invalid(Problems(problems)));
+        }
+
+        Ok(config)
+    }
+
+    /// Serializes the configuration as TOML, including the keys which take their default value
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(&Value::try_from(self)?)
+    }
+
+    fn from_table(source: Table) -> Result<Config, ConfigError> {
+        let mut unknown = Vec::new();
+        let mut config: Config =
+            serde_ignored::deserialize(Value::Table(source.clone()), |path| {
+                unknown.push(registry::unknown_key(&path.to_string()))
+            })?;
+
+        if !unknown.is_empty() {
+            return Err(ConfigError::Invalid(Problems(unknown)));
+        }
+
+        config.source = source;
+        Ok(config)
+    }
+}
+
+/// Merges the tables recursively. Any other value replaces the one on the base table.
+fn merge(base: &mut Table, overrides: &Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(key), value) {
+            (Some(Value::Table(base)), Value::Table(overrides)) => merge(base, overrides),
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Filters of the linted objects. Objects matching any of the `ignored_*` filters are never
+/// linted. Otherwise, they have to match all the `allowed_*` filters and the selector.
+#[derive(Default, Deserialize, Serialize, Debug)]
 pub struct KorrecteConfig {
+    /// Glob patterns, or regular expressions enclosed in slashes, of the linted namespaces
+    #[serde(default)]
     pub(crate) allowed_namespaces: Vec<String>,
+    #[serde(default)]
     pub(crate) ignored_namespaces: Vec<String>,
+    /// Regular expressions of the names of the linted objects
+    #[serde(default)]
+    pub(crate) allowed_names: Vec<String>,
+    #[serde(default)]
+    pub(crate) ignored_names: Vec<String>,
+    /// Kinds of the linted objects
+    #[serde(default)]
+    pub(crate) allowed_kinds: Vec<String>,
+    #[serde(default)]
+    pub(crate) ignored_kinds: Vec<String>,
+    /// Label selector expression of the linted objects, like `app=web,tier in (frontend)`
+    #[serde(default)]
+    pub(crate) selector: Option<String>,
+}
+
+/// Object filters set from outside of the configuration file, like the command line. Defined
+/// filters replace the ones of the configuration.
+#[derive(Default, Debug)]
+pub struct ObjectFilters {
+    pub namespaces: Option<Vec<String>>,
+    pub ignored_namespaces: Option<Vec<String>>,
+    pub names: Option<Vec<String>>,
+    pub ignored_names: Option<Vec<String>>,
+    pub kinds: Option<Vec<String>>,
+    pub ignored_kinds: Option<Vec<String>>,
+    pub selector: Option<String>,
+}
+
+impl KorrecteConfig {
+    /// Problems of the object filters which could not be parsed
+    pub(crate) fn filter_problems(&self) -> Vec<String> {
+        ObjectFilter::new(self).problems().to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, ConfigError};
+
+    #[test]
+    fn it_rejects_unknown_sections_and_keys() {
+        let source = r#"
+[required_lables]
+labels = ["app"]
+
+[scripts]
+timeout_ms = 10
+max_operation = 10
+"#;
+
+        match Config::from_toml(source) {
+            Err(ConfigError::Invalid(problems)) => assert_eq!(
+                "  - Unknown section `required_lables`. Did you mean `required_labels`?\n  - Unknown key `max_operation` on section `scripts`. Did you mean `max_operations`?",
+                problems.to_string()
+            ),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_serializes_the_resolved_configuration() {
+        let source = r#"
+[scripts]
+timeout_ms = 10
+
+[[required_labels.schemas]]
+[[required_labels.schemas.labels]]
+key = "team"
+pattern = "[a-z]+"
+"#;
+        let config = Config::from_toml(source).unwrap();
+
+        let resolved = config.to_toml().unwrap();
+        assert!(resolved.contains("timeout_ms = 10\n"));
+        assert!(resolved.contains("max_operations = 1000000\n"));
+        assert!(resolved.contains("max_fuel = "));
+        assert!(resolved.contains("pattern = \"[a-z]+\"\n"));
+        assert!(Config::from_toml(&resolved).is_ok());
+    }
+
+    #[test]
+    fn it_accepts_the_sample_configuration() {
+        let source = std::fs::read_to_string("../korrecte.toml").unwrap();
+
+        assert!(Config::from_toml(&source).is_ok());
+    }
 }
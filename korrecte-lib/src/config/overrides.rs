@@ -0,0 +1,224 @@
+use super::registry;
+use super::{merge, Config, ConfigError, Problems};
+use crate::kube::label_selector::LabelSelectorExt;
+use crate::kube::namespace::namespace_matches;
+use crate::linters::{KubeObjectType, LintSpecLoader, Severity};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use toml::value::Table;
+
+/// Block of configuration which only applies to the objects living on the given namespaces and
+/// matching the given label selector. When several blocks match an object, they are applied in
+/// the order they are defined.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub(crate) struct Override {
+    /// Glob patterns of the namespaces of the matched objects. If empty, all namespaces match.
+    #[serde(default)]
+    namespaces: Vec<String>,
+    /// Label selector of the matched objects. If not defined, all objects match.
+    #[serde(default)]
+    selector: Option<LabelSelector>,
+    /// Lints which are not executed on the matched objects
+    #[serde(default)]
+    pub(crate) disabled: Vec<String>,
+    /// Lints executed on the matched objects, even if a previous block disabled them
+    #[serde(default)]
+    pub(crate) enabled: Vec<String>,
+    /// Severity of the findings of each lint
+    #[serde(default)]
+    pub(crate) severity: HashMap<String, Severity>,
+    /// Configuration of the lints, merged on top of the sections with the same name
+    #[serde(default)]
+    pub(crate) config: Table,
+}
+
+impl Override {
+    pub(crate) fn matches(&self, object: &KubeObjectType) -> bool {
+        let metadata = object.metadata();
+        let namespace = match object {
+            KubeObjectType::CoreV1Namespace(_) => metadata.and_then(|m| m.name.as_ref()),
+            _ => metadata.and_then(|m| m.namespace.as_ref()),
+        };
+        let labels = metadata.and_then(|m| m.labels.as_ref());
+
+        namespace_matches(&self.namespaces, namespace)
+            && self
+                .selector
+                .as_ref()
+                .map(|selector| selector.matches(labels))
+                .unwrap_or(true)
+    }
+}
+
+impl Config {
+    /// Configuration resulting of merging the lint configuration of the given blocks, in order,
+    /// on top of this one
+    pub(crate) fn with_overrides(&self, blocks: &[&Override]) -> Result<Config, ConfigError> {
+        let mut source = self.source.clone();
+        for block in blocks {
+            merge(&mut source, &block.config);
+        }
+
+        let mut config = Config::from_table(source)?;
+        // Files referenced by the configuration are only loaded once
+        config.custom_rules = self.custom_rules.clone();
+        config.scripts = self.scripts.clone();
+        config.plugins = self.plugins.clone();
+
+        Ok(config)
+    }
+
+    /// Checks that the overrides only configure lints and that the result of applying each of
+    /// them is a valid configuration
+    pub(super) fn override_problems(&self) -> Vec<String> {
+        let overridable: Vec<&str> = registry::sections()
+            .into_iter()
+            .filter(|section| section.overridable)
+            .map(|section| section.name)
+            .collect();
+
+        let mut problems = Vec::new();
+        for (index, block) in self.overrides.iter().enumerate() {
+            let unknown: Vec<&String> = block
+                .config
+                .keys()
+                .filter(|name| !overridable.contains(&name.as_str()))
+                .collect();
+
+            for name in &unknown {
+                problems.push(format!(
+                    "Override {}: section `{}` can not be overridden",
+                    index, name
+                ));
+            }
+            if !unknown.is_empty() {
+                continue;
+            }
+
+            match self.with_overrides(&[block]) {
+                Ok(_) => {}
+                Err(ConfigError::Invalid(Problems(invalid))) => problems.extend(
+                    invalid
+                        .into_iter()
+                        .map(|problem| format!("Override {}: {}", index, problem)),
+                ),
+                Err(e) => problems.push(format!("Override {}: {}", index, e)),
+            }
+        }
+
+        problems
+    }
+
+    /// Checks that the lints referenced by the overrides exist
+    pub(crate) fn check_override_lints(&self, specs: &LintSpecLoader) -> Result<(), ConfigError> {
+        let names: Vec<&str> = specs.all().keys().map(String::as_str).collect();
+
+        let mut problems = Vec::new();
+        for (index, block) in self.overrides.iter().enumerate() {
+            let referenced = block
+                .disabled
+                .iter()
+                .chain(block.enabled.iter())
+                .chain(block.severity.keys());
+
+            for lint in referenced.filter(|lint| specs.get(lint).is_none()) {
+                problems.push(registry::with_suggestion(
+                    format!("Override {}: unknown lint `{}`", index, lint),
+                    lint,
+                    &names,
+                ));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(Problems(problems)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{Config, ConfigError};
+    use crate::linters::KubeObjectType;
+    use toml::Value;
+
+    #[test]
+    fn it_merges_lint_configuration() {
+        let config = Config::from_toml(
+            r#"
+[required_labels]
+labels = ["app"]
+
+[hardcoded_secrets]
+entropy_threshold = 4.0
+min_entropy_length = 10
+
+[[overrides]]
+namespaces = ["team-*"]
+[overrides.config.hardcoded_secrets]
+min_entropy_length = 30
+"#,
+        )
+        .unwrap();
+
+        let overridden = config.with_overrides(&[&config.overrides[0]]).unwrap();
+        let secrets = &overridden.source["hardcoded_secrets"];
+
+        assert_eq!(Value::Integer(30), secrets["min_entropy_length"]);
+        assert_eq!(Value::Float(4.0), secrets["entropy_threshold"]);
+        assert_eq!(
+            Value::Array(vec![Value::String("app".to_string())]),
+            overridden.source["required_labels"]["labels"]
+        );
+    }
+
+    #[test]
+    fn it_matches_by_namespace_and_labels() {
+        let config = Config::from_toml(
+            r#"
+[[overrides]]
+namespaces = ["monitoring"]
+selector = { matchLabels = { app = "prometheus" } }
+"#,
+        )
+        .unwrap();
+        let block = &config.overrides[0];
+
+        assert!(block.matches(&pod("monitoring", "prometheus")));
+        assert!(!block.matches(&pod("monitoring", "grafana")));
+        assert!(!block.matches(&pod("default", "prometheus")));
+    }
+
+    #[test]
+    fn it_rejects_invalid_overrides() {
+        let source = r#"
+[[overrides]]
+[overrides.config.plugins]
+directory = "plugins"
+
+[[overrides]]
+[overrides.config.required_labels]
+lables = ["team"]
+"#;
+
+        match Config::from_toml(source) {
+            Err(ConfigError::Invalid(problems)) => assert_eq!(
+                "  - Override 0: section `plugins` can not be overridden\n  - Override 1: Unknown key `lables` on section `required_labels`. Did you mean `labels`?",
+                problems.to_string()
+            ),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    fn pod(namespace: &str, app: &str) -> KubeObjectType {
+        let yaml = format!(
+            "metadata:\n  name: pod\n  namespace: {}\n  labels:\n    app: {}\nspec:\n  containers: []",
+            namespace, app
+        );
+
+        KubeObjectType::from_yaml(&yaml, "v1", "Pod").unwrap()
+    }
+}
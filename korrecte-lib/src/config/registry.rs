@@ -0,0 +1,177 @@
+use super::{KorrecteConfig, Override};
+use crate::linters::custom::Config as CustomRulesConfig;
+use crate::linters::lints::environment_passwords::Config as EnvironmentPasswordsConfig;
+use crate::linters::lints::hardcoded_secrets::Config as HardcodedSecretsConfig;
+use crate::linters::lints::pod_requirements::Config as PodRequirementsConfig;
+use crate::linters::lints::required_labels::Config as RequiredLabelsConfig;
+use crate::linters::plugins::Config as PluginsConfig;
+use crate::linters::scripting::Config as ScriptsConfig;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+/// Maximum edit distance between an unknown name and a known one to suggest the latter
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+/// Section of the configuration file, together with the keys accepted by its type
+pub(crate) struct Section {
+    pub name: &'static str,
+    pub keys: &'static [&'static str],
+    /// Whether the section configures lints, and thus can be overridden for some objects
+    pub overridable: bool,
+}
+
+impl Section {
+    fn of<'de, T: Deserialize<'de>>(name: &'static str) -> Self {
+        Section {
+            name,
+            keys: fields::<T>(),
+            overridable: false,
+        }
+    }
+
+    fn lint<'de, T: Deserialize<'de>>(name: &'static str) -> Self {
+        Section {
+            overridable: true,
+            ..Self::of::<T>(name)
+        }
+    }
+}
+
+/// Sections accepted on the configuration file. Every lint with configuration has to register
+/// its type here, so its keys can be validated.
+pub(crate) fn sections() -> Vec<Section> {
+    vec![
+        Section::of::<KorrecteConfig>("korrecte"),
+        Section::lint::<RequiredLabelsConfig>("required_labels"),
+        Section::lint::<EnvironmentPasswordsConfig>("environment_passwords"),
+        Section::lint::<PodRequirementsConfig>("pod_requirements"),
+        Section::lint::<HardcodedSecretsConfig>("hardcoded_secrets"),
+        Section::of::<CustomRulesConfig>("custom_rules"),
+        Section::of::<ScriptsConfig>("scripts"),
+        Section::of::<PluginsConfig>("plugins"),
+        // Array of tables: keys are the ones of each of its elements
+        Section::of::<Override>("overrides"),
+    ]
+}
+
+/// Describes a key of the configuration which is not used by any section, suggesting the
+/// closest known name when there is one
+pub(crate) fn unknown_key(path: &str) -> String {
+    let sections = sections();
+    let mut segments = path.splitn(2, '.');
+    let section_name = segments.next().unwrap_or_default();
+
+    let section = match sections.iter().find(|section| section.name == section_name) {
+        Some(section) => section,
+        None => {
+            let names: Vec<&str> = sections.iter().map(|section| section.name).collect();
+            return with_suggestion(
+                format!("Unknown section `{}`", section_name),
+                section_name,
+                &names,
+            );
+        }
+    };
+
+    let key = segments.next().unwrap_or_default();
+    let message = format!("Unknown key `{}` on section `{}`", key, section.name);
+    // Keys of the elements of arrays of tables are prefixed by their index
+    let field = match key.split_once('.') {
+        Some((index, field)) if index.parse::<usize>().is_ok() => field,
+        _ => key,
+    };
+    if field.contains('.') {
+        // Suggestions are only given for the keys defined directly on the section
+        return message;
+    }
+
+    with_suggestion(message, field, section.keys)
+}
+
+pub(crate) fn with_suggestion(message: String, name: &str, candidates: &[&str]) -> String {
+    let suggestion = candidates
+        .iter()
+        .map(|candidate| (levenshtein::levenshtein(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(distance, _)| *distance);
+
+    match suggestion {
+        Some((_, candidate)) => format!("{}. Did you mean `{}`?", message, candidate),
+        None => message,
+    }
+}
+
+/// Names of the fields of the given struct, as they are named by its `Deserialize`
+/// implementation
+fn fields<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldsCollector {
+        fields: &mut fields,
+    });
+
+    fields
+}
+
+/// Deserializer which never produces a value, but records the fields requested by
+/// `deserialize_struct`
+struct FieldsCollector<'a> {
+    fields: &'a mut &'static [&'static str],
+}
+
+impl<'de> Deserializer<'de> for FieldsCollector<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.fields = fields;
+
+        Err(de::Error::custom("fields collected"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier
+        ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fields, sections, unknown_key};
+    use crate::config::Config;
+
+    #[test]
+    fn every_section_is_registered() {
+        let registered: Vec<&str> = sections().iter().map(|section| section.name).collect();
+
+        assert_eq!(fields::<Config>(), registered.as_slice());
+    }
+
+    #[test]
+    fn it_suggests_known_names() {
+        assert_eq!(
+            "Unknown section `required_lables`. Did you mean `required_labels`?",
+            unknown_key("required_lables")
+        );
+        assert_eq!(
+            "Unknown key `lables` on section `required_labels`. Did you mean `labels`?",
+            unknown_key("required_labels.lables")
+        );
+        assert_eq!(
+            "Unknown key `timeout` on section `plugins`",
+            unknown_key("plugins.timeout")
+        );
+        assert_eq!(
+            "Unknown key `1.namespace` on section `overrides`. Did you mean `namespaces`?",
+            unknown_key("overrides.1.namespace")
+        );
+    }
+}
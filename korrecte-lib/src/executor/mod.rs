@@ -1,20 +1,16 @@
-use crate::config::Config;
+use crate::config::{Config, ConfigLayers};
+use crate::fix::{self, FileFixes};
 use crate::kube::repository::api_async::FrozenObjectRepository;
 use crate::kube::repository::file::FileObjectRepository;
 use crate::kube::repository::ObjectRepository;
 use crate::linters::evaluator::{Context, Evaluator, SingleEvaluator};
-use crate::linters::LintCollection;
+use crate::linters::{LintCollection, LintSpecLoader};
 use crate::reporting::{Reporter, SingleThreadedReporter};
-use anyhow::Result;
-use std::fs::File;
-use std::io::Read;
+use crate::snapshot::Snapshot;
+use anyhow::{anyhow, Result};
 use std::path::Path;
 
-#[derive(Debug)]
-pub enum ConfigError {
-    CouldNotLoadError,
-    CouldNotParseError,
-}
+pub use crate::config::ConfigError;
 
 pub enum ExecutionMode<'a> {
     Api,
@@ -28,8 +24,16 @@ pub struct ExecutionContextBuilder<'a> {
 }
 
 impl<'a> ExecutionContextBuilder<'a> {
-    pub fn configuration_from_path(mut self, path: &Path) -> Result<ExecutionContextBuilder<'a>> {
-        let config = Self::load_config_from_filesystem(path)?;
+    pub fn configuration_from_path(self, path: &Path) -> Result<ExecutionContextBuilder<'a>> {
+        self.configuration_from_layers(&ConfigLayers::default().file(path)?)
+    }
+
+    /// Uses the configuration resulting of merging all the given layers
+    pub fn configuration_from_layers(
+        mut self,
+        layers: &ConfigLayers,
+    ) -> Result<ExecutionContextBuilder<'a>> {
+        let config = Self::load_config_files(layers.config()?)?;
         self.configuration = Some(config);
 
         Ok(self)
@@ -48,12 +52,13 @@ impl<'a> ExecutionContextBuilder<'a> {
         }
     }
 
-    fn load_config_from_filesystem(path: &Path) -> Result<Config> {
-        let mut file = File::open(path)?;
-        let mut buffer = String::new();
-        file.read_to_string(&mut buffer)?;
+    fn load_config_files(mut config: Config) -> Result<Config> {
+        config.custom_rules.load_files()?;
+        config.scripts.load_files()?;
+        config.plugins.load_files()?;
+        config.check_override_lints(&LintSpecLoader::with_config(&config)?)?;
 
-        Ok(toml::from_str(&buffer)?)
+        Ok(config)
     }
 }
 
@@ -62,6 +67,13 @@ pub struct ExecutionContext<'a> {
     configuration: Config,
 }
 
+impl ExecutionContext<'_> {
+    /// Specs of all the lints which will be executed, including the custom rules
+    pub fn lint_specs(&self) -> Result<LintSpecLoader> {
+        LintSpecLoader::with_config(&self.configuration)
+    }
+}
+
 pub struct Executor<'a> {
     context: ExecutionContext<'a>,
 }
@@ -75,7 +87,7 @@ impl<'a> Executor<'a> {
         let reporter = SingleThreadedReporter::default();
         let object_repository = self.load_object_repository()?;
         let lints = LintCollection::all(&self.context.configuration);
-        let evaluator = SingleEvaluator;
+        let evaluator = SingleEvaluator::new(&self.context.configuration);
 
         let context = Context {
             repository: &*object_repository,
@@ -88,6 +100,28 @@ impl<'a> Executor<'a> {
         Ok(reporter)
     }
 
+    /// Applies the fixes suggested by the lints to each of the manifest files. Unsafe fixes are
+    /// only applied if `allow_unsafe` is set. Files are not written: the caller decides what to do
+    /// with the fixed contents.
+    pub fn fix(self, allow_unsafe: bool) -> Result<Vec<Result<FileFixes>>> {
+        let path = match self.context.mode {
+            ExecutionMode::FileSystem(path) => path,
+            ExecutionMode::Api => return Err(anyhow!("Fixes can only be applied on files")),
+        };
+        if Snapshot::is_snapshot(path) {
+            return Err(anyhow!("Fixes can not be applied on snapshots"));
+        }
+
+        let repository = FileObjectRepository::new(path)?;
+        let lints = LintCollection::all(&self.context.configuration);
+        let config = &self.context.configuration;
+
+        Ok(fix::manifest_files(path)?
+            .iter()
+            .map(|file| fix::fix_file(file, &lints, &repository, config, allow_unsafe))
+            .collect())
+    }
+
     fn load_object_repository(&self) -> Result<Box<dyn ObjectRepository>> {
         match self.context.mode {
             ExecutionMode::FileSystem(path) => {
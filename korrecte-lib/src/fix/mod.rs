@@ -0,0 +1,227 @@
+use crate::config::Config;
+use crate::kube::repository::file::KubeObjectLoader;
+use crate::kube::repository::ObjectRepository;
+use crate::linters::evaluator::{Context, SingleEvaluator};
+use crate::linters::LintList;
+use crate::reporting::{Finding, Reporter, SingleThreadedReporter};
+use anyhow::{anyhow, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod yaml;
+
+/// Upper bound of fixes applied on a single file, to avoid looping forever on lints which keep
+/// suggesting fixes
+const MAX_FIXES_PER_FILE: usize = 1000;
+
+/// Result of applying the suggested fixes to a manifest file
+pub struct FileFixes {
+    pub path: PathBuf,
+    pub original: String,
+    pub fixed: String,
+    /// Findings whose fix has been applied
+    pub applied: Vec<Finding>,
+    /// Findings whose fix could not be applied preserving the format of the file
+    pub skipped: Vec<Finding>,
+    /// Findings whose fix is unsafe and has not been applied
+    pub unsafe_skipped: Vec<Finding>,
+}
+
+impl FileFixes {
+    pub fn has_changes(&self) -> bool {
+        self.original != self.fixed
+    }
+}
+
+/// Applies the fixes suggested by the lints to the given file. Objects are linted again after
+/// each fix, so every patch is applied on the same content it was generated for. Unsafe fixes are
+/// only applied if `allow_unsafe` is set. Files which can not be parsed are never modified.
+pub(crate) fn fix_file(
+    path: &Path,
+    lints: &LintList,
+    repository: &dyn ObjectRepository,
+    config: &Config,
+    allow_unsafe: bool,
+) -> Result<FileFixes> {
+    let original = fs::read_to_string(path)?;
+    if let Err(e) = yaml::parse(&original) {
+        return Err(anyhow!("Refusing to fix {}: {}", path.display(), e));
+    }
+
+    let mut fixed = original.clone();
+    let mut applied = Vec::new();
+    let mut skipped = Vec::new();
+    let mut unsafe_skipped = Vec::new();
+    let mut attempted = HashSet::new();
+    let evaluator = SingleEvaluator::new(config);
+
+    while applied.len() < MAX_FIXES_PER_FILE {
+        let next = KubeObjectLoader::read(&fixed)?
+            .iter()
+            .enumerate()
+            .filter_map(|(index, object)| Some((index, object.as_ref().ok()?)))
+            .filter(|(_, object)| evaluator.needs_linting(object))
+            .flat_map(|(index, object)| {
+                let reporter = SingleThreadedReporter::default();
+                let context = Context {
+                    repository,
+                    reporter: &reporter,
+                    config: &config.korrecte,
+                };
+                evaluator.evaluate_object(&context, lints, object);
+
+                reporter
+                    .findings()
+                    .into_iter()
+                    .map(move |finding| (index, finding))
+            })
+            .find(|(index, finding)| match finding.fix() {
+                Some(fix) => !attempted.contains(&attempt_key(*index, finding, &fix.to_string())),
+                None => false,
+            });
+
+        let (index, finding, fix) = match next {
+            Some((index, finding)) => match finding.fix().cloned() {
+                Some(fix) => (index, finding, fix),
+                None => break,
+            },
+            None => break,
+        };
+
+        attempted.insert(attempt_key(index, &finding, &fix.to_string()));
+        if finding.is_fix_unsafe() && !allow_unsafe {
+            unsafe_skipped.push(finding);
+            continue;
+        }
+
+        match yaml::apply(&fixed, index, &fix) {
+            Ok(edited) => {
+                fixed = edited;
+                applied.push(finding);
+            }
+            Err(_) => skipped.push(finding),
+        }
+    }
+
+    Ok(FileFixes {
+        path: path.to_path_buf(),
+        original,
+        fixed,
+        applied,
+        skipped,
+        unsafe_skipped,
+    })
+}
+
+fn attempt_key(index: usize, finding: &Finding, fix: &str) -> String {
+    format!("{}:{}:{}", index, finding.lint_name(), fix)
+}
+
+/// Files containing the manifests found on the given path
+pub(crate) fn manifest_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if path.is_dir() {
+        let mut files: Vec<PathBuf> = path
+            .read_dir()?
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| path.is_file())
+            .collect();
+        files.sort();
+
+        Ok(files)
+    } else if path.is_file() {
+        Ok(vec![path.to_path_buf()])
+    } else {
+        Err(anyhow!("Could not find manifests on {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fix_file;
+    use crate::config::Config;
+    use crate::kube::repository::file::FileObjectRepository;
+    use crate::linters::LintCollection;
+    use crate::reporting::Finding;
+    use std::path::Path;
+
+    #[test]
+    fn it_fixes_files_preserving_the_format() {
+        let path = Path::new("../tests/fix/manifests.yaml");
+        let config = Config::default();
+        let repository = FileObjectRepository::new(path).unwrap();
+        let lints = LintCollection::all(&config);
+
+        let fixes = fix_file(path, &lints, &repository, &config, true).unwrap();
+
+        assert_eq!(
+            vec![
+                "never_restart_with_liveness_probe",
+                "required_labels",
+                "statefulset_no_grace_period"
+            ],
+            lint_names(&fixes.applied)
+        );
+        assert!(fixes.skipped.is_empty());
+        assert!(fixes.unsafe_skipped.is_empty());
+
+        let expected = fixes
+            .original
+            .replace(
+                "      livenessProbe:\n        exec:\n          command: [\"true\"]\n",
+                "",
+            )
+            .replace(
+                "    app: batch # Owner application\n",
+                "    app: batch # Owner application\n    role: changeme\n",
+            )
+            .replace(
+                "terminationGracePeriodSeconds: 0 # Fast shutdown",
+                "terminationGracePeriodSeconds: 30 # Fast shutdown",
+            );
+        assert_eq!(expected, fixes.fixed);
+    }
+
+    #[test]
+    fn it_only_applies_safe_fixes_by_default() {
+        let path = Path::new("../tests/fix/manifests.yaml");
+        let config = Config::default();
+        let repository = FileObjectRepository::new(path).unwrap();
+        let lints = LintCollection::all(&config);
+
+        let fixes = fix_file(path, &lints, &repository, &config, false).unwrap();
+
+        assert_eq!(
+            vec!["statefulset_no_grace_period"],
+            lint_names(&fixes.applied)
+        );
+        assert_eq!(
+            vec!["never_restart_with_liveness_probe", "required_labels"],
+            lint_names(&fixes.unsafe_skipped)
+        );
+
+        let expected = fixes.original.replace(
+            "terminationGracePeriodSeconds: 0 # Fast shutdown",
+            "terminationGracePeriodSeconds: 30 # Fast shutdown",
+        );
+        assert_eq!(expected, fixes.fixed);
+    }
+
+    #[test]
+    fn it_refuses_to_fix_files_with_parse_errors() {
+        let path = Path::new("../tests/fix/invalid.yaml");
+        let config = Config::default();
+        let repository =
+            FileObjectRepository::new(Path::new("../tests/fix/manifests.yaml")).unwrap();
+        let lints = LintCollection::all(&config);
+
+        assert!(fix_file(path, &lints, &repository, &config, false).is_err());
+    }
+
+    fn lint_names(findings: &[Finding]) -> Vec<&str> {
+        findings
+            .iter()
+            .map(|finding| finding.lint_name().as_str())
+            .collect()
+    }
+}
@@ -0,0 +1,635 @@
+//! Formatting preserving edition of YAML manifests. Documents are parsed to a tree which keeps the
+//! position of each node, and JSON patch operations are translated to edits on the lines of the
+//! source, so comments, key order and formatting of the untouched nodes are kept.
+//!
+//! Edits are verified by parsing the result again: any edit which does not produce the same value
+//! as applying the patch to the original document is rejected.
+
+use anyhow::{anyhow, Context, Result};
+use json_patch::{Patch, PatchOperation};
+use serde_json::{Number, Value};
+use std::fmt;
+use yaml_rust::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust::scanner::{Marker, TScalarStyle};
+use yaml_rust::Yaml;
+
+/// Position of a node on the source. Lines and columns start at 0 and columns are measured in
+/// characters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Position {
+    line: usize,
+    col: usize,
+}
+
+impl From<Marker> for Position {
+    fn from(marker: Marker) -> Self {
+        Position {
+            line: marker.line().saturating_sub(1),
+            col: marker.col(),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Node {
+    Scalar {
+        position: Position,
+        style: TScalarStyle,
+        value: String,
+    },
+    Mapping {
+        flow: bool,
+        entries: Vec<Entry>,
+    },
+    Sequence {
+        items: Vec<Node>,
+    },
+    Alias {
+        position: Position,
+    },
+}
+
+#[derive(Debug)]
+struct Entry {
+    key: String,
+    position: Position,
+    value: Node,
+}
+
+impl Node {
+    /// Last line which contains the start of any node of the tree
+    fn last_line(&self) -> Option<usize> {
+        match self {
+            Node::Scalar { position, .. } | Node::Alias { position } => Some(position.line),
+            Node::Mapping { entries, .. } => entries
+                .last()
+                .and_then(|e| e.value.last_line().or(Some(e.position.line))),
+            Node::Sequence { items, .. } => items.last().and_then(Node::last_line),
+        }
+    }
+
+    fn get(&self, segment: &str) -> Option<&Node> {
+        match self {
+            Node::Mapping { entries, .. } => {
+                entries.iter().find(|e| e.key == segment).map(|e| &e.value)
+            }
+            Node::Sequence { items, .. } => items.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            Node::Scalar {
+                style: TScalarStyle::Plain,
+                value,
+                ..
+            } => yaml_to_json(&Yaml::from_str(value)),
+            Node::Scalar { value, .. } => Value::String(value.clone()),
+            Node::Mapping { entries, .. } => Value::Object(
+                entries
+                    .iter()
+                    .map(|e| (e.key.clone(), e.value.to_json()))
+                    .collect(),
+            ),
+            Node::Sequence { items, .. } => Value::Array(items.iter().map(Node::to_json).collect()),
+            Node::Alias { .. } => Value::Null,
+        }
+    }
+}
+
+fn yaml_to_json(yaml: &Yaml) -> Value {
+    match yaml {
+        Yaml::Integer(i) => Value::Number((*i).into()),
+        Yaml::Real(r) => r
+            .parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(Value::Number)
+            .unwrap_or_else(|| Value::String(r.clone())),
+        Yaml::Boolean(b) => Value::Bool(*b),
+        Yaml::String(s) => Value::String(s.clone()),
+        _ => Value::Null,
+    }
+}
+
+/// YAML document along with the positions of its nodes
+pub(crate) struct Document {
+    root: Node,
+    value: Value,
+}
+
+enum Partial {
+    Mapping {
+        flow: bool,
+        entries: Vec<Entry>,
+        key: Option<(String, Position)>,
+    },
+    Sequence {
+        items: Vec<Node>,
+    },
+}
+
+struct Builder<'a> {
+    lines: &'a [Vec<char>],
+    stack: Vec<Partial>,
+    documents: Vec<Node>,
+}
+
+impl Builder<'_> {
+    fn char_at(&self, position: Position) -> Option<char> {
+        self.lines.get(position.line)?.get(position.col).cloned()
+    }
+
+    fn push(&mut self, node: Node) {
+        match self.stack.last_mut() {
+            None => self.documents.push(node),
+            Some(Partial::Sequence { items, .. }) => items.push(node),
+            Some(Partial::Mapping { entries, key, .. }) => match key.take() {
+                Some((key, position)) => entries.push(Entry {
+                    key,
+                    position,
+                    value: node,
+                }),
+                None => {
+                    // Complex keys are not used on manifests. They are kept as empty keys, which
+                    // can not be addressed by the patches.
+                    *key = match node {
+                        Node::Scalar {
+                            value, position, ..
+                        } => Some((value, position)),
+                        _ => Some((String::new(), Position { line: 0, col: 0 })),
+                    };
+                }
+            },
+        }
+    }
+}
+
+impl MarkedEventReceiver for Builder<'_> {
+    fn on_event(&mut self, event: Event, marker: Marker) {
+        let position = Position::from(marker);
+
+        match event {
+            Event::Scalar(value, style, _, _) => self.push(Node::Scalar {
+                position,
+                style,
+                value,
+            }),
+            Event::Alias(_) => self.push(Node::Alias { position }),
+            Event::MappingStart(_) => self.stack.push(Partial::Mapping {
+                flow: self.char_at(position) == Some('{'),
+                entries: Vec::new(),
+                key: None,
+            }),
+            Event::SequenceStart(_) => self.stack.push(Partial::Sequence { items: Vec::new() }),
+            Event::MappingEnd | Event::SequenceEnd => {
+                let node = match self.stack.pop() {
+                    Some(Partial::Mapping { flow, entries, .. }) => Node::Mapping { flow, entries },
+                    Some(Partial::Sequence { items }) => Node::Sequence { items },
+                    None => return,
+                };
+                self.push(node);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Parses all the documents of the given source
+pub(crate) fn parse(source: &str) -> Result<Vec<Document>> {
+    let lines: Vec<Vec<char>> = source.lines().map(|l| l.chars().collect()).collect();
+    let mut builder = Builder {
+        lines: &lines,
+        stack: Vec::new(),
+        documents: Vec::new(),
+    };
+
+    Parser::new(source.chars()).load(&mut builder, true)?;
+
+    Ok(builder
+        .documents
+        .into_iter()
+        .map(|root| Document {
+            value: root.to_json(),
+            root,
+        })
+        .collect())
+}
+
+/// Applies the patch on the document with the given index, returning the edited source
+pub(crate) fn apply(source: &str, index: usize, patch: &Patch) -> Result<String> {
+    let documents = parse(source)?;
+    let document = documents
+        .get(index)
+        .ok_or_else(|| anyhow!("Document {} not found", index))?;
+
+    let mut expected = document.value.clone();
+    json_patch::patch(&mut expected, patch)?;
+
+    let mut edited = source.to_string();
+    for operation in patch.iter() {
+        let documents = parse(&edited)?;
+        let mut lines = Lines::new(&edited);
+        apply_operation(&mut lines, &documents[index].root, operation)?;
+        edited = lines.to_string();
+    }
+
+    let result = parse(&edited).context("Edited manifest could not be parsed")?;
+    let preserved = result.len() == documents.len()
+        && result
+            .iter()
+            .zip(documents.iter())
+            .enumerate()
+            .all(|(i, (after, before))| {
+                &after.value == if i == index { &expected } else { &before.value }
+            });
+    if !preserved {
+        return Err(anyhow!("Patch can not be applied preserving the format"));
+    }
+
+    Ok(edited)
+}
+
+fn apply_operation(lines: &mut Lines, root: &Node, operation: &PatchOperation) -> Result<()> {
+    match operation {
+        PatchOperation::Add(op) => {
+            let (parent, key) = parent(root, &op.path)?;
+            set(lines, parent, &key, &op.value)
+        }
+        PatchOperation::Replace(op) => {
+            let (parent, key) = parent(root, &op.path)?;
+            set(lines, parent, &key, &op.value)
+        }
+        PatchOperation::Remove(op) => {
+            let (parent, key) = parent(root, &op.path)?;
+            remove(lines, parent, &key)
+        }
+        PatchOperation::Test(_) => Ok(()),
+        _ => Err(anyhow!("Unsupported patch operation: {}", operation)),
+    }
+}
+
+/// Finds the parent node of the given JSON pointer, along with the last segment of the pointer
+fn parent<'a>(root: &'a Node, pointer: &str) -> Result<(&'a Node, String)> {
+    let mut segments: Vec<String> = pointer
+        .split('/')
+        .skip(1)
+        .map(|s| s.replace("~1", "/").replace("~0", "~"))
+        .collect();
+    let key = segments
+        .pop()
+        .ok_or_else(|| anyhow!("Documents can not be replaced"))?;
+
+    let parent = segments
+        .iter()
+        .try_fold(root, |node, segment| node.get(segment))
+        .ok_or_else(|| anyhow!("Path not found: {}", pointer))?;
+
+    Ok((parent, key))
+}
+
+/// Returns the entries of the given node, if it is a mapping in block style
+fn block_entries(node: &Node) -> Result<&[Entry]> {
+    match node {
+        Node::Mapping {
+            flow: false,
+            entries,
+        } => Ok(entries),
+        _ => Err(anyhow!("Only mappings in block style can be edited")),
+    }
+}
+
+fn set(lines: &mut Lines, parent: &Node, key: &str, value: &Value) -> Result<()> {
+    let entries = block_entries(parent)?;
+    let indent = entries
+        .first()
+        .map(|e| e.position.col)
+        .ok_or_else(|| anyhow!("Empty mappings can not be edited"))?;
+
+    let entry = match entries.iter().find(|e| e.key == key) {
+        Some(entry) => entry,
+        None => {
+            let last_line = parent
+                .last_line()
+                .ok_or_else(|| anyhow!("Could not find the end of the mapping"))?;
+            lines.insert(last_line + 1, render_entry(key, value, indent));
+            return Ok(());
+        }
+    };
+
+    let is_collection = value.is_object() || value.is_array();
+    if let (
+        false,
+        Node::Scalar {
+            position, style, ..
+        },
+    ) = (is_collection, &entry.value)
+    {
+        let line = lines.get(position.line)?;
+        let end = scalar_end(&line, position.col, *style)
+            .ok_or_else(|| anyhow!("Could not find the end of the scalar"))?;
+        return lines.replace(position.line, position.col, end, &render_scalar(value));
+    }
+
+    let last_line = entry.value.last_line().unwrap_or(entry.position.line);
+    let prefix = lines.prefix(entry.position)?;
+    let mut rendered = render_entry(key, value, indent);
+    rendered[0] = format!("{}{}", prefix, &rendered[0][indent..]);
+    lines.remove(entry.position.line, last_line);
+    lines.insert(entry.position.line, rendered);
+
+    Ok(())
+}
+
+fn remove(lines: &mut Lines, parent: &Node, key: &str) -> Result<()> {
+    let entries = block_entries(parent)?;
+    if entries.len() < 2 {
+        return Err(anyhow!("Mappings can not be left empty"));
+    }
+
+    let entry = entries
+        .iter()
+        .find(|e| e.key == key)
+        .ok_or_else(|| anyhow!("Key not found: {}", key))?;
+    if !lines.prefix(entry.position)?.trim().is_empty() {
+        return Err(anyhow!("Key {} does not start its line", key));
+    }
+
+    let last_line = entry.value.last_line().unwrap_or(entry.position.line);
+    lines.remove(entry.position.line, last_line);
+
+    Ok(())
+}
+
+/// Column where the scalar starting at the given column ends. Only scalars contained on a single
+/// line are supported.
+fn scalar_end(line: &[char], start: usize, style: TScalarStyle) -> Option<usize> {
+    match style {
+        TScalarStyle::DoubleQuoted => {
+            let mut i = start + 1;
+            while i < line.len() {
+                match line[i] {
+                    '\\' => i += 2,
+                    '"' => return Some(i + 1),
+                    _ => i += 1,
+                }
+            }
+            None
+        }
+        TScalarStyle::SingleQuoted => {
+            let mut i = start + 1;
+            while i < line.len() {
+                match (line[i], line.get(i + 1)) {
+                    ('\'', Some('\'')) => i += 2,
+                    ('\'', _) => return Some(i + 1),
+                    _ => i += 1,
+                }
+            }
+            None
+        }
+        TScalarStyle::Plain => {
+            let comment = (start..line.len())
+                .find(|&i| line[i] == '#' && i > start && line[i - 1].is_whitespace())
+                .unwrap_or(line.len());
+            let end = (start..comment)
+                .rev()
+                .find(|&i| !line[i].is_whitespace())
+                .map(|i| i + 1)?;
+            Some(end)
+        }
+        _ => None,
+    }
+}
+
+/// Renders a `key: value` entry, in block style, indented with the given amount of spaces
+fn render_entry(key: &str, value: &Value, indent: usize) -> Vec<String> {
+    let padding = " ".repeat(indent);
+    let key = render_string(key);
+
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            let mut lines = vec![format!("{}{}:", padding, key)];
+            for (key, value) in map {
+                lines.extend(render_entry(key, value, indent + 2));
+            }
+            lines
+        }
+        Value::Array(items) if !items.is_empty() => {
+            let mut lines = vec![format!("{}{}:", padding, key)];
+            for item in items {
+                lines.extend(render_item(item, indent + 2));
+            }
+            lines
+        }
+        _ => vec![format!("{}{}: {}", padding, key, render_scalar(value))],
+    }
+}
+
+/// Renders an item of a sequence, in block style, indented with the given amount of spaces
+fn render_item(value: &Value, indent: usize) -> Vec<String> {
+    let mut lines = match value {
+        Value::Object(map) if !map.is_empty() => map
+            .iter()
+            .flat_map(|(key, value)| render_entry(key, value, indent + 2))
+            .collect(),
+        Value::Array(items) if !items.is_empty() => items
+            .iter()
+            .flat_map(|item| render_item(item, indent + 2))
+            .collect(),
+        _ => vec![format!(
+            "{}{}",
+            " ".repeat(indent + 2),
+            render_scalar(value)
+        )],
+    };
+    lines[0] = format!("{}- {}", " ".repeat(indent), &lines[0][indent + 2..]);
+
+    lines
+}
+
+fn render_scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => render_string(s),
+        Value::Object(_) => "{}".to_string(),
+        Value::Array(_) => "[]".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+/// Renders strings as plain scalars when they would not be confused with other types. Otherwise,
+/// they are rendered as JSON strings, which are valid double quoted scalars.
+fn render_string(s: &str) -> String {
+    let is_plain = s.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./".contains(c))
+        && Yaml::from_str(s).as_str().is_some();
+
+    if is_plain {
+        s.to_string()
+    } else {
+        Value::String(s.to_string()).to_string()
+    }
+}
+
+/// Lines of a source, which keeps its original line endings
+struct Lines {
+    lines: Vec<String>,
+    newline: &'static str,
+    trailing_newline: bool,
+}
+
+impl Lines {
+    fn new(source: &str) -> Self {
+        Lines {
+            lines: source.lines().map(str::to_string).collect(),
+            newline: if source.contains("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            },
+            trailing_newline: source.ends_with('\n'),
+        }
+    }
+
+    fn get(&self, line: usize) -> Result<Vec<char>> {
+        self.lines
+            .get(line)
+            .map(|l| l.chars().collect())
+            .ok_or_else(|| anyhow!("Line {} out of bounds", line))
+    }
+
+    /// Text of the line which precedes the given position
+    fn prefix(&self, position: Position) -> Result<String> {
+        Ok(self.get(position.line)?.iter().take(position.col).collect())
+    }
+
+    /// Replaces the characters of the line between the given columns
+    fn replace(&mut self, line: usize, start: usize, end: usize, text: &str) -> Result<()> {
+        let chars = self.get(line)?;
+        let before: String = chars[..start].iter().collect();
+        let after: String = chars[end..].iter().collect();
+        self.lines[line] = format!("{}{}{}", before, text, after);
+
+        Ok(())
+    }
+
+    /// Removes all the lines between `first` and `last`, both included
+    fn remove(&mut self, first: usize, last: usize) {
+        let last = last.min(self.lines.len().saturating_sub(1));
+        self.lines.drain(first..=last);
+    }
+
+    fn insert(&mut self, at: usize, lines: Vec<String>) {
+        let at = at.min(self.lines.len());
+        self.lines.splice(at..at, lines);
+    }
+}
+
+impl fmt::Display for Lines {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.lines.join(self.newline))?;
+        if self.trailing_newline {
+            f.write_str(self.newline)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::apply;
+    use json_patch::Patch;
+    use serde_json::json;
+
+    const MANIFEST: &str = r#"# Frontend
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: web   # Public name
+  labels: {app: web}
+spec:
+  template:
+    spec:
+      terminationGracePeriodSeconds: 0
+      containers:
+        - name: web
+          image: "nginx:1.17"
+          livenessProbe:
+            httpGet:
+              path: /health
+          # Always pull
+          imagePullPolicy: Always
+---
+apiVersion: v1
+kind: Service
+metadata:
+  name: web
+"#;
+
+    fn patch(value: serde_json::Value) -> Patch {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn it_preserves_comments_and_order() {
+        let fix = patch(json!([
+            { "op": "remove", "path": "/spec/template/spec/containers/0/livenessProbe" },
+            { "op": "replace", "path": "/spec/template/spec/terminationGracePeriodSeconds", "value": 30 },
+            { "op": "replace", "path": "/metadata/name", "value": "frontend" },
+            { "op": "add", "path": "/metadata/annotations", "value": { "team": "web", "app.kubernetes.io/part-of": "1" } },
+        ]));
+
+        let edited = apply(MANIFEST, 0, &fix).unwrap();
+
+        let expected = r#"# Frontend
+apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: frontend   # Public name
+  labels: {app: web}
+  annotations:
+    app.kubernetes.io/part-of: "1"
+    team: web
+spec:
+  template:
+    spec:
+      terminationGracePeriodSeconds: 30
+      containers:
+        - name: web
+          image: "nginx:1.17"
+          # Always pull
+          imagePullPolicy: Always
+---
+apiVersion: v1
+kind: Service
+metadata:
+  name: web
+"#;
+        assert_eq!(expected, edited);
+    }
+
+    #[test]
+    fn it_edits_the_given_document() {
+        let fix = patch(json!([{ "op": "add", "path": "/metadata/namespace", "value": "shop" }]));
+
+        let edited = apply(MANIFEST, 1, &fix).unwrap();
+
+        assert!(edited.ends_with("metadata:\n  name: web\n  namespace: shop\n"));
+        assert!(edited.starts_with(&MANIFEST[..MANIFEST.find("---").unwrap()]));
+    }
+
+    #[test]
+    fn it_rejects_unsafe_edits() {
+        let flow = patch(json!([{ "op": "add", "path": "/metadata/labels/tier", "value": "a" }]));
+        let empty = patch(
+            json!([{ "op": "remove", "path": "/spec/template/spec/containers/0/livenessProbe/httpGet/path" }]),
+        );
+        let missing = patch(json!([{ "op": "remove", "path": "/spec/replicas" }]));
+
+        assert!(apply(MANIFEST, 0, &flow).is_err());
+        assert!(apply(MANIFEST, 0, &empty).is_err());
+        assert!(apply(MANIFEST, 0, &missing).is_err());
+    }
+}
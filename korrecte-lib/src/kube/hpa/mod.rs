@@ -0,0 +1,262 @@
+use crate::kube::repository::ObjectRepository;
+use crate::linters::KubeObjectType;
+use crate::{f, m};
+use k8s_openapi::api::autoscaling::v1::HorizontalPodAutoscaler;
+use k8s_openapi::api::autoscaling::v2beta1::HorizontalPodAutoscaler as HpaV2beta1;
+use k8s_openapi::api::autoscaling::v2beta2::HorizontalPodAutoscaler as HpaV2beta2;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use log::*;
+use yaml_rust::Yaml;
+
+const DEFAULT_MIN_REPLICAS: i32 = 1;
+const RESOURCE_METRIC: &str = "Resource";
+const CONTAINER_RESOURCE_METRIC: &str = "ContainerResource";
+
+/// Object scaled by an HPA
+pub(crate) struct ScaleTarget<'a> {
+    pub api_version: Option<&'a String>,
+    pub kind: &'a str,
+    pub name: &'a str,
+}
+
+/// Resource (`cpu`, `memory`, ...) used as a scaling metric. `ContainerResource` metrics only
+/// take into account the usage of the given container.
+pub(crate) struct ResourceMetric {
+    pub resource: String,
+    pub container: Option<String>,
+}
+
+impl ResourceMetric {
+    fn pod(resource: &str) -> Self {
+        ResourceMetric {
+            resource: resource.to_string(),
+            container: None,
+        }
+    }
+}
+
+/// Common view over the different HPA versions, which are exposed as unrelated types by
+/// `k8s_openapi`.
+pub(crate) trait HpaExt {
+    fn metadata(&self) -> Option<&ObjectMeta>;
+    fn scale_target(&self) -> Option<ScaleTarget<'_>>;
+    fn min_replicas(&self) -> i32;
+    fn max_replicas(&self) -> Option<i32>;
+    /// Returns the resources used as scaling metrics
+    fn resource_metrics(&self) -> Vec<ResourceMetric>;
+
+    /// Checks if the HPA scales the object with the given kind and name on the given namespace
+    fn targets(&self, kind: &str, name: &str, namespace: Option<&String>) -> bool {
+        let same_namespace = f!(self.metadata(), namespace) == namespace;
+
+        self.scale_target()
+            .map(|target| same_namespace && target.kind == kind && target.name == name)
+            .unwrap_or(false)
+    }
+}
+
+impl HpaExt for HorizontalPodAutoscaler {
+    fn metadata(&self) -> Option<&ObjectMeta> {
+        self.metadata.as_ref()
+    }
+
+    fn scale_target(&self) -> Option<ScaleTarget<'_>> {
+        self.spec.as_ref().map(|spec| ScaleTarget {
+            api_version: spec.scale_target_ref.api_version.as_ref(),
+            kind: &spec.scale_target_ref.kind,
+            name: &spec.scale_target_ref.name,
+        })
+    }
+
+    fn min_replicas(&self) -> i32 {
+        f!(self.spec, min_replicas)
+            .cloned()
+            .unwrap_or(DEFAULT_MIN_REPLICAS)
+    }
+
+    fn max_replicas(&self) -> Option<i32> {
+        self.spec.as_ref().map(|spec| spec.max_replicas)
+    }
+
+    fn resource_metrics(&self) -> Vec<ResourceMetric> {
+        f!(self.spec, target_cpu_utilization_percentage)
+            .map(|_| vec![ResourceMetric::pod("cpu")])
+            .unwrap_or_default()
+    }
+}
+
+impl HpaExt for HpaV2beta1 {
+    fn metadata(&self) -> Option<&ObjectMeta> {
+        self.metadata.as_ref()
+    }
+
+    fn scale_target(&self) -> Option<ScaleTarget<'_>> {
+        self.spec.as_ref().map(|spec| ScaleTarget {
+            api_version: spec.scale_target_ref.api_version.as_ref(),
+            kind: &spec.scale_target_ref.kind,
+            name: &spec.scale_target_ref.name,
+        })
+    }
+
+    fn min_replicas(&self) -> i32 {
+        f!(self.spec, min_replicas)
+            .cloned()
+            .unwrap_or(DEFAULT_MIN_REPLICAS)
+    }
+
+    fn max_replicas(&self) -> Option<i32> {
+        self.spec.as_ref().map(|spec| spec.max_replicas)
+    }
+
+    fn resource_metrics(&self) -> Vec<ResourceMetric> {
+        f!(self.spec, metrics)
+            .iter()
+            .flat_map(|metrics| metrics.iter())
+            .filter(|metric| metric.type_ == RESOURCE_METRIC)
+            .filter_map(|metric| m!(metric.resource, name))
+            .map(|name| ResourceMetric::pod(name))
+            .collect()
+    }
+}
+
+impl HpaExt for HpaV2beta2 {
+    fn metadata(&self) -> Option<&ObjectMeta> {
+        self.metadata.as_ref()
+    }
+
+    fn scale_target(&self) -> Option<ScaleTarget<'_>> {
+        self.spec.as_ref().map(|spec| ScaleTarget {
+            api_version: spec.scale_target_ref.api_version.as_ref(),
+            kind: &spec.scale_target_ref.kind,
+            name: &spec.scale_target_ref.name,
+        })
+    }
+
+    fn min_replicas(&self) -> i32 {
+        f!(self.spec, min_replicas)
+            .cloned()
+            .unwrap_or(DEFAULT_MIN_REPLICAS)
+    }
+
+    fn max_replicas(&self) -> Option<i32> {
+        self.spec.as_ref().map(|spec| spec.max_replicas)
+    }
+
+    // The source of `ContainerResource` metrics is exposed as a resource source when the object
+    // is loaded from a file, see `expose_container_resource_metrics`
+    fn resource_metrics(&self) -> Vec<ResourceMetric> {
+        let mut resource_metrics = Vec::new();
+
+        for metric in f!(self.spec, metrics).iter().flat_map(|m| m.iter()) {
+            let name = m!(metric.resource, name);
+            match (metric.type_.as_str(), name) {
+                (RESOURCE_METRIC, Some(name)) => resource_metrics.push(ResourceMetric::pod(name)),
+                (CONTAINER_RESOURCE_METRIC, Some(name)) => {
+                    resource_metrics.push(container_resource_metric(name))
+                }
+                (CONTAINER_RESOURCE_METRIC, None) => warn!(
+                    "Dropping container resource metric of HPA {:?}: its source is not available",
+                    f!(self.metadata, name)
+                ),
+                _ => {}
+            }
+        }
+
+        resource_metrics
+    }
+}
+
+// Container names can not contain slashes, so the first one separates the container from the
+// resource name, which may contain slashes on extended resources
+fn container_resource_metric(name: &str) -> ResourceMetric {
+    let mut parts = name.splitn(2, '/');
+    match (parts.next(), parts.next()) {
+        (Some(container), Some(resource)) => ResourceMetric {
+            resource: resource.to_string(),
+            container: Some(container.to_string()),
+        },
+        _ => ResourceMetric::pod(name),
+    }
+}
+
+/// `ContainerResource` metrics are not available on the api version supported by `k8s_openapi`,
+/// so their `containerResource` source would be dropped when the HPA is deserialized. This copies
+/// the target of the metric to the `resource` source, keeping the `ContainerResource` type, and
+/// stores the container and the resource as `<container>/<name>` on its name, so the resource used
+/// to scale can be retrieved.
+pub(crate) fn expose_container_resource_metrics(hpa: &mut Yaml) {
+    let metrics = match hpa {
+        Yaml::Hash(hpa) => match hpa.get_mut(&Yaml::String("spec".to_string())) {
+            Some(Yaml::Hash(spec)) => spec.get_mut(&Yaml::String("metrics".to_string())),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    let metrics = match metrics {
+        Some(Yaml::Array(metrics)) => metrics,
+        _ => return,
+    };
+
+    for metric in metrics.iter_mut() {
+        if metric["type"].as_str() != Some(CONTAINER_RESOURCE_METRIC) {
+            continue;
+        }
+
+        let source = &metric["containerResource"];
+        let name = match (source["container"].as_str(), source["name"].as_str()) {
+            (Some(container), Some(name)) => format!("{}/{}", container, name),
+            (None, Some(name)) => name.to_string(),
+            _ => continue,
+        };
+
+        let mut resource = yaml_rust::yaml::Hash::new();
+        resource.insert(Yaml::String("name".to_string()), Yaml::String(name));
+        if !source["target"].is_badvalue() {
+            resource.insert(Yaml::String("target".to_string()), source["target"].clone());
+        }
+
+        if let Yaml::Hash(metric) = metric {
+            metric.insert(Yaml::String("resource".to_string()), Yaml::Hash(resource));
+        }
+    }
+}
+
+/// Returns all the HPAs stored on the repository, regardless of their api version
+pub(crate) fn all_hpas(repository: &dyn ObjectRepository) -> Vec<&dyn HpaExt> {
+    repository
+        .iter()
+        .filter_map(|object| match object {
+            KubeObjectType::AutoscalingV1HorizontalPodAutoscaler(hpa) => {
+                Some(hpa.as_ref() as &dyn HpaExt)
+            }
+            KubeObjectType::AutoscalingV2beta1HorizontalPodAutoscaler(hpa) => {
+                Some(hpa.as_ref() as &dyn HpaExt)
+            }
+            KubeObjectType::AutoscalingV2beta2HorizontalPodAutoscaler(hpa) => {
+                Some(hpa.as_ref() as &dyn HpaExt)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Finds the object scaled by the HPA. Only objects on the same namespace can be scaled. If the
+/// target does not define its api version, any object with the same kind is considered.
+pub(crate) fn find_scale_target<'a>(
+    repository: &'a dyn ObjectRepository,
+    hpa: &dyn HpaExt,
+) -> Option<&'a KubeObjectType> {
+    let target = hpa.scale_target()?;
+    let namespace = f!(hpa.metadata(), namespace);
+
+    repository.iter().find(|object| {
+        let name = f!(object.metadata(), name).map(|name| name.as_str());
+        let same_type = match target.api_version {
+            Some(api_version) => object.matches_type(api_version, target.kind),
+            None => object.kind() == target.kind,
+        };
+
+        same_type && name == Some(target.name) && f!(object.metadata(), namespace) == namespace
+    })
+}
@@ -0,0 +1,152 @@
+use crate::f;
+use crate::kube::repository::ObjectRepository;
+use crate::linters::KubeObjectType;
+use k8s_openapi::api::extensions::v1beta1::Ingress as LegacyIngress;
+use k8s_openapi::api::networking::v1beta1::Ingress;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use std::collections::{BTreeMap, HashSet};
+
+/// Backend referenced by an ingress, together with the rule that routes to it. The default
+/// backend of the ingress is represented without host nor path.
+pub(crate) struct IngressBackendRef {
+    pub host: Option<String>,
+    pub path: Option<String>,
+    pub service_name: String,
+    pub service_port: IntOrString,
+}
+
+/// Common view over the different ingress versions, which share the same structure but are
+/// exposed as unrelated types by `k8s_openapi`.
+pub(crate) trait IngressExt {
+    fn metadata(&self) -> Option<&ObjectMeta>;
+    fn rule_backends(&self) -> Vec<IngressBackendRef>;
+    fn default_backend(&self) -> Option<IngressBackendRef>;
+    fn tls_secret_names(&self) -> Vec<String>;
+
+    fn is_alb(&self) -> bool {
+        self.has_alb_annotation(self.metadata().and_then(|m| m.annotations.as_ref()))
+    }
+
+    fn get_service_names(&self) -> HashSet<String> {
+        self.rule_backends()
+            .into_iter()
+            .map(|backend| backend.service_name)
+            .collect()
+    }
+
+    fn has_alb_annotation(&self, annotations: Option<&BTreeMap<String, String>>) -> bool {
+        annotations
+            .and_then(|a| a.get("kubernetes.io/ingress.class"))
+            .map(|class| class == "alb")
+            .unwrap_or(false)
+    }
+}
+
+impl IngressExt for Ingress {
+    fn metadata(&self) -> Option<&ObjectMeta> {
+        self.metadata.as_ref()
+    }
+
+    fn rule_backends(&self) -> Vec<IngressBackendRef> {
+        let empty = Vec::new();
+
+        f!(self.spec, rules)
+            .unwrap_or(&empty)
+            .iter()
+            .flat_map(|rule| {
+                rule.http
+                    .as_ref()
+                    .map(|http| {
+                        http.paths
+                            .iter()
+                            .map(|path| IngressBackendRef {
+                                host: rule.host.clone(),
+                                path: path.path.clone(),
+                                service_name: path.backend.service_name.clone(),
+                                service_port: path.backend.service_port.clone(),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_else(Vec::new)
+            })
+            .collect()
+    }
+
+    fn default_backend(&self) -> Option<IngressBackendRef> {
+        f!(self.spec, backend).map(|backend| IngressBackendRef {
+            host: None,
+            path: None,
+            service_name: backend.service_name.clone(),
+            service_port: backend.service_port.clone(),
+        })
+    }
+
+    fn tls_secret_names(&self) -> Vec<String> {
+        f!(self.spec, tls)
+            .map(|tls| tls.iter().filter_map(|t| t.secret_name.clone()).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl IngressExt for LegacyIngress {
+    fn metadata(&self) -> Option<&ObjectMeta> {
+        self.metadata.as_ref()
+    }
+
+    fn rule_backends(&self) -> Vec<IngressBackendRef> {
+        let empty = Vec::new();
+
+        f!(self.spec, rules)
+            .unwrap_or(&empty)
+            .iter()
+            .flat_map(|rule| {
+                rule.http
+                    .as_ref()
+                    .map(|http| {
+                        http.paths
+                            .iter()
+                            .map(|path| IngressBackendRef {
+                                host: rule.host.clone(),
+                                path: path.path.clone(),
+                                service_name: path.backend.service_name.clone(),
+                                service_port: path.backend.service_port.clone(),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_else(Vec::new)
+            })
+            .collect()
+    }
+
+    fn default_backend(&self) -> Option<IngressBackendRef> {
+        f!(self.spec, backend).map(|backend| IngressBackendRef {
+            host: None,
+            path: None,
+            service_name: backend.service_name.clone(),
+            service_port: backend.service_port.clone(),
+        })
+    }
+
+    fn tls_secret_names(&self) -> Vec<String> {
+        f!(self.spec, tls)
+            .map(|tls| tls.iter().filter_map(|t| t.secret_name.clone()).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Returns all the ingresses stored on the repository, regardless of their api version
+pub(crate) fn all_ingresses(repository: &dyn ObjectRepository) -> Vec<&dyn IngressExt> {
+    repository
+        .iter()
+        .filter_map(|object| match object {
+            KubeObjectType::NetworkingV1beta1Ingress(ingress) => {
+                Some(ingress.as_ref() as &dyn IngressExt)
+            }
+            KubeObjectType::ExtensionsV1beta1Ingress(ingress) => {
+                Some(ingress.as_ref() as &dyn IngressExt)
+            }
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,240 @@
+use crate::kube::labels::{is_valid_key, is_valid_value};
+use anyhow::{anyhow, Result};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, LabelSelectorRequirement};
+use std::collections::BTreeMap;
+
+pub(crate) trait LabelSelectorExt {
+    /// Checks if the given labels are selected by both `matchLabels` and `matchExpressions`.
+    /// Note that an empty selector matches with any set of labels.
+    fn matches(&self, labels: Option<&BTreeMap<String, String>>) -> bool;
+    fn is_empty(&self) -> bool;
+}
+
+impl LabelSelectorExt for LabelSelector {
+    fn matches(&self, labels: Option<&BTreeMap<String, String>>) -> bool {
+        let empty = BTreeMap::new();
+        let labels = labels.unwrap_or(&empty);
+
+        let match_labels = self
+            .match_labels
+            .iter()
+            .flatten()
+            .all(|(key, value)| labels.get(key) == Some(value));
+
+        let match_expressions = self
+            .match_expressions
+            .iter()
+            .flatten()
+            .all(|requirement| requirement_matches(requirement, labels));
+
+        match_labels && match_expressions
+    }
+
+    fn is_empty(&self) -> bool {
+        self.match_labels
+            .as_ref()
+            .map(|l| l.is_empty())
+            .unwrap_or(true)
+            && self
+                .match_expressions
+                .as_ref()
+                .map(|e| e.is_empty())
+                .unwrap_or(true)
+    }
+}
+
+fn requirement_matches(
+    requirement: &LabelSelectorRequirement,
+    labels: &BTreeMap<String, String>,
+) -> bool {
+    let value = labels.get(&requirement.key);
+    let values = requirement.values.as_ref();
+    let contained = |v: &String| values.map(|vs| vs.contains(v)).unwrap_or(false);
+
+    match requirement.operator.as_str() {
+        "In" => value.map(contained).unwrap_or(false),
+        "NotIn" => value.map(|v| !contained(v)).unwrap_or(true),
+        "Exists" => value.is_some(),
+        "DoesNotExist" => value.is_none(),
+        // Unknown operators are rejected by the api server, so we consider they don't match
+        _ => false,
+    }
+}
+
+/// Parses a label selector expression, as accepted by `kubectl --selector`: a comma separated
+/// list of requirements like `app=web`, `tier!=db`, `env in (prod,staging)`, `env notin (dev)`,
+/// `team` or `!deprecated`
+pub(crate) fn parse_selector(expression: &str) -> Result<LabelSelector> {
+    let requirements = split_requirements(expression)
+        .into_iter()
+        .map(|requirement| parse_requirement(requirement.trim()))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(LabelSelector {
+        match_labels: None,
+        match_expressions: Some(requirements),
+    })
+}
+
+/// Splits the expression on the commas which are not inside a set of values
+fn split_requirements(expression: &str) -> Vec<&str> {
+    let mut requirements = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (position, c) in expression.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                requirements.push(&expression[start..position]);
+                start = position + 1;
+            }
+            _ => {}
+        }
+    }
+    requirements.push(&expression[start..]);
+
+    requirements
+}
+
+fn parse_requirement(requirement: &str) -> Result<LabelSelectorRequirement> {
+    let invalid = || anyhow!("Invalid label selector requirement `{}`", requirement);
+
+    let (key, operator, values) = if let Some(key) = requirement.strip_prefix('!') {
+        (key.trim(), "DoesNotExist", Vec::new())
+    } else if let Some(position) = requirement.find('(') {
+        let (head, set) = requirement.split_at(position);
+        let set = set
+            .trim_end()
+            .strip_prefix('(')
+            .and_then(|set| set.strip_suffix(')'))
+            .ok_or_else(invalid)?;
+        let values = set
+            .split(',')
+            .map(|value| value.trim().to_string())
+            .collect();
+
+        let mut words = head.split_whitespace();
+        let key = words.next().ok_or_else(invalid)?;
+        let operator = match words.next() {
+            Some("in") => "In",
+            Some("notin") => "NotIn",
+            _ => return Err(invalid()),
+        };
+        if words.next().is_some() {
+            return Err(invalid());
+        }
+
+        (key, operator, values)
+    } else if let Some(position) = requirement.find("!=") {
+        let value = requirement[position + 2..].trim().to_string();
+        (requirement[..position].trim(), "NotIn", vec![value])
+    } else if let Some(position) = requirement.find('=') {
+        let value = requirement[position + 1..].trim_start_matches('=').trim();
+        (
+            requirement[..position].trim(),
+            "In",
+            vec![value.to_string()],
+        )
+    } else {
+        (requirement, "Exists", Vec::new())
+    };
+
+    if !is_valid_key(key) || !values.iter().all(|value| is_valid_value(value)) {
+        return Err(invalid());
+    }
+
+    Ok(LabelSelectorRequirement {
+        key: key.to_string(),
+        operator: operator.to_string(),
+        values: Some(values),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_selector, LabelSelectorExt};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, LabelSelectorRequirement};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn it_matches_labels_and_expressions() {
+        let selector = LabelSelector {
+            match_labels: Some(labels(&[("app", "web")])),
+            match_expressions: Some(vec![
+                requirement("tier", "In", &["frontend", "backend"]),
+                requirement("env", "NotIn", &["dev"]),
+                requirement("team", "Exists", &[]),
+                requirement("deprecated", "DoesNotExist", &[]),
+            ]),
+        };
+
+        let matching = labels(&[("app", "web"), ("tier", "frontend"), ("team", "a")]);
+        assert!(selector.matches(Some(&matching)));
+
+        let wrong_tier = labels(&[("app", "web"), ("tier", "db"), ("team", "a")]);
+        assert!(!selector.matches(Some(&wrong_tier)));
+
+        let excluded_env = labels(&[("app", "web"), ("tier", "backend"), ("env", "dev")]);
+        assert!(!selector.matches(Some(&excluded_env)));
+
+        let deprecated = labels(&[
+            ("app", "web"),
+            ("tier", "backend"),
+            ("team", "a"),
+            ("deprecated", "true"),
+        ]);
+        assert!(!selector.matches(Some(&deprecated)));
+    }
+
+    #[test]
+    fn empty_selector_matches_everything() {
+        let selector = LabelSelector::default();
+
+        assert!(selector.is_empty());
+        assert!(selector.matches(None));
+        assert!(selector.matches(Some(&labels(&[("app", "web")]))));
+    }
+
+    #[test]
+    fn it_parses_selector_expressions() {
+        let selector =
+            parse_selector("app=web, tier in (frontend, backend),env!=dev,team,!deprecated")
+                .unwrap();
+
+        let matching = labels(&[("app", "web"), ("tier", "frontend"), ("team", "a")]);
+        assert!(selector.matches(Some(&matching)));
+
+        let excluded_env = labels(&[("app", "web"), ("tier", "backend"), ("env", "dev")]);
+        assert!(!selector.matches(Some(&excluded_env)));
+
+        let selector = parse_selector("app==web,env notin (dev,test)").unwrap();
+        assert!(selector.matches(Some(&labels(&[("app", "web")]))));
+        assert!(!selector.matches(Some(&labels(&[("app", "web"), ("env", "test")]))));
+    }
+
+    #[test]
+    fn it_rejects_invalid_selector_expressions() {
+        assert!(parse_selector("").is_err());
+        assert!(parse_selector("app=web,").is_err());
+        assert!(parse_selector("env in dev").is_err());
+        assert!(parse_selector("env within (dev)").is_err());
+        assert!(parse_selector("app=we b").is_err());
+    }
+
+    fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn requirement(key: &str, operator: &str, values: &[&str]) -> LabelSelectorRequirement {
+        LabelSelectorRequirement {
+            key: key.to_string(),
+            operator: operator.to_string(),
+            values: Some(values.iter().map(|v| v.to_string()).collect()),
+        }
+    }
+}
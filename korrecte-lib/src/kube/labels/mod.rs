@@ -0,0 +1,76 @@
+const MAX_NAME_LENGTH: usize = 63;
+const MAX_PREFIX_LENGTH: usize = 253;
+
+/// Validates label and annotation keys against the Kubernetes grammar: an optional DNS subdomain
+/// prefix followed by a slash and a name of, at most, 63 characters
+pub(crate) fn is_valid_key(key: &str) -> bool {
+    let (prefix, name) = match key.rfind('/') {
+        Some(position) => (Some(&key[..position]), &key[position + 1..]),
+        None => (None, key),
+    };
+
+    prefix.map(is_valid_prefix).unwrap_or(true) && is_valid_name(name)
+}
+
+/// Validates label values, which may be empty or a name of, at most, 63 characters
+pub(crate) fn is_valid_value(value: &str) -> bool {
+    value.is_empty() || is_valid_name(value)
+}
+
+/// Names begin and end with an alphanumeric character, with dashes, underscores, dots and
+/// alphanumerics between
+fn is_valid_name(name: &str) -> bool {
+    let inner_valid = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+
+    name.len() <= MAX_NAME_LENGTH
+        && inner_valid
+        && has_alphanumeric_edges(name, char::is_ascii_alphanumeric)
+}
+
+/// Prefixes are DNS subdomains: lowercase labels separated by dots
+fn is_valid_prefix(prefix: &str) -> bool {
+    let is_lower_alphanumeric = |c: &char| c.is_ascii_lowercase() || c.is_ascii_digit();
+
+    prefix.len() <= MAX_PREFIX_LENGTH
+        && prefix.split('.').all(|label| {
+            label.chars().all(|c| is_lower_alphanumeric(&c) || c == '-')
+                && has_alphanumeric_edges(label, is_lower_alphanumeric)
+        })
+}
+
+fn has_alphanumeric_edges(value: &str, is_alphanumeric: impl Fn(&char) -> bool) -> bool {
+    let first = value.chars().next();
+    let last = value.chars().last();
+
+    first.map(|c| is_alphanumeric(&c)).unwrap_or(false)
+        && last.map(|c| is_alphanumeric(&c)).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_valid_key, is_valid_value};
+
+    #[test]
+    fn it_validates_keys() {
+        assert!(is_valid_key("app"));
+        assert!(is_valid_key("app.kubernetes.io/name"));
+        assert!(is_valid_key("example.com/My_Label.v1"));
+        assert!(!is_valid_key(""));
+        assert!(!is_valid_key("-app"));
+        assert!(!is_valid_key("Example.com/app"));
+        assert!(!is_valid_key("example.com/"));
+        assert!(!is_valid_key("a/b/c"));
+        assert!(!is_valid_key(&"a".repeat(64)));
+    }
+
+    #[test]
+    fn it_validates_values() {
+        assert!(is_valid_value(""));
+        assert!(is_valid_value("v1.2.3"));
+        assert!(!is_valid_value("has spaces"));
+        assert!(!is_valid_value("ends-with-"));
+        assert!(!is_valid_value(&"a".repeat(64)));
+    }
+}
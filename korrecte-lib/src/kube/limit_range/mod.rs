@@ -0,0 +1,86 @@
+use crate::f;
+use crate::kube::repository::ObjectRepository;
+use crate::linters::KubeObjectType;
+use k8s_openapi::api::core::v1::{Container, LimitRangeItem};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use std::collections::BTreeMap;
+
+const CONTAINER_LIMIT_TYPE: &str = "Container";
+
+/// Requests and limits a container will have once admitted, after applying the defaults of the
+/// `LimitRange` objects of its namespace.
+#[derive(Default)]
+pub(crate) struct EffectiveResources {
+    pub requests: BTreeMap<String, Quantity>,
+    pub limits: BTreeMap<String, Quantity>,
+}
+
+/// Computes the resources of the container after applying the defaults declared on the
+/// `LimitRange` objects of the given namespace. Values declared on the container always take
+/// precedence over the defaults.
+pub(crate) fn effective_resources(
+    repository: &dyn ObjectRepository,
+    namespace: Option<&String>,
+    container: &Container,
+) -> EffectiveResources {
+    let mut resources = EffectiveResources {
+        requests: f!(container.resources, requests)
+            .cloned()
+            .unwrap_or_default(),
+        limits: f!(container.resources, limits).cloned().unwrap_or_default(),
+    };
+
+    for item in container_limit_items(repository, namespace) {
+        let (default_limits, default_requests) = item_defaults(item);
+
+        for (resource, value) in default_limits {
+            resources.limits.entry(resource).or_insert(value);
+        }
+        for (resource, value) in default_requests {
+            resources.requests.entry(resource).or_insert(value);
+        }
+    }
+
+    resources
+}
+
+fn container_limit_items<'a>(
+    repository: &'a dyn ObjectRepository,
+    namespace: Option<&'a String>,
+) -> impl Iterator<Item = &'a LimitRangeItem> + 'a {
+    repository
+        .iter()
+        .filter_map(move |object| match object {
+            KubeObjectType::CoreV1LimitRange(limit_range)
+                if f!(limit_range.metadata, namespace) == namespace =>
+            {
+                limit_range.spec.as_ref()
+            }
+            _ => None,
+        })
+        .flat_map(|spec| spec.limits.iter())
+        .filter(|item| item.type_.as_deref() == Some(CONTAINER_LIMIT_TYPE))
+}
+
+/// Returns the default limits and requests of the item, following the same defaulting rules that
+/// the api server applies to `LimitRange` objects: the default limit falls back to `max`, and the
+/// default request falls back to the default limit and then to `min`.
+fn item_defaults(
+    item: &LimitRangeItem,
+) -> (BTreeMap<String, Quantity>, BTreeMap<String, Quantity>) {
+    let mut limits = item.default.clone().unwrap_or_default();
+    for (resource, value) in item.max.iter().flatten() {
+        limits
+            .entry(resource.clone())
+            .or_insert_with(|| value.clone());
+    }
+
+    let mut requests = item.default_request.clone().unwrap_or_default();
+    for (resource, value) in limits.iter().chain(item.min.iter().flatten()) {
+        requests
+            .entry(resource.clone())
+            .or_insert_with(|| value.clone());
+    }
+
+    (limits, requests)
+}
@@ -1,2 +1,13 @@
+pub(crate) mod hpa;
+pub(crate) mod ingress;
+pub(crate) mod label_selector;
+pub(crate) mod labels;
+pub(crate) mod limit_range;
+pub(crate) mod namespace;
+pub(crate) mod network_policy;
+pub(crate) mod owner;
+pub(crate) mod pdb;
+pub(crate) mod quantity;
+pub(crate) mod rbac;
 pub(crate) mod repository;
 pub(crate) mod service;
@@ -0,0 +1,71 @@
+/// Checks if the namespace matches any of the given glob patterns. An empty list of patterns
+/// matches all the namespaces, including cluster wide objects, which do not have any namespace.
+pub(crate) fn namespace_matches(patterns: &[String], namespace: Option<&String>) -> bool {
+    if patterns.is_empty() {
+        return true;
+    }
+
+    namespace
+        .map(|namespace| patterns.iter().any(|p| glob_matches(p, namespace)))
+        .unwrap_or(false)
+}
+
+/// Matches the value against a glob pattern, where `*` matches any sequence of characters and `?`
+/// matches exactly one character
+pub(crate) fn glob_matches(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+    let (mut p, mut v) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, v));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match backtrack {
+                // Let the last star consume one more character
+                Some((star, consumed)) => {
+                    p = star + 1;
+                    v = consumed + 1;
+                    backtrack = Some((star, consumed + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{glob_matches, namespace_matches};
+
+    #[test]
+    fn it_matches_glob_patterns() {
+        assert!(glob_matches("prod-*", "prod-eu"));
+        assert!(glob_matches("*-eu", "prod-eu"));
+        assert!(glob_matches("team-?", "team-a"));
+        assert!(glob_matches("*", ""));
+        assert!(glob_matches("a*b*c", "axxbyybc"));
+        assert!(!glob_matches("prod-*", "staging-eu"));
+        assert!(!glob_matches("team-?", "team-ab"));
+        assert!(!glob_matches("prod", "production"));
+    }
+
+    #[test]
+    fn empty_patterns_match_all_namespaces() {
+        let production = "production".to_string();
+
+        assert!(namespace_matches(&[], None));
+        assert!(namespace_matches(&[], Some(&production)));
+        assert!(namespace_matches(&["prod*".to_string()], Some(&production)));
+        assert!(!namespace_matches(&["prod*".to_string()], None));
+    }
+}
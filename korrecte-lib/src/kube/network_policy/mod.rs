@@ -0,0 +1,60 @@
+use crate::f;
+use crate::kube::label_selector::LabelSelectorExt;
+use crate::kube::repository::ObjectRepository;
+use crate::linters::KubeObjectType;
+use k8s_openapi::api::networking::v1::NetworkPolicy;
+use std::collections::BTreeMap;
+
+pub(crate) trait NetworkPolicyExt {
+    /// Checks if the policy restricts the ingress traffic of the selected pods. When
+    /// `policyTypes` is not set, ingress is always restricted.
+    fn applies_to_ingress(&self) -> bool;
+    /// Checks if the policy selects all the pods of its namespace and denies all the
+    /// ingress traffic
+    fn is_default_deny(&self) -> bool;
+    fn selects(&self, pod_labels: Option<&BTreeMap<String, String>>) -> bool;
+}
+
+impl NetworkPolicyExt for NetworkPolicy {
+    fn applies_to_ingress(&self) -> bool {
+        f!(self.spec, policy_types)
+            .map(|types| types.iter().any(|t| t == "Ingress"))
+            .unwrap_or(true)
+    }
+
+    fn is_default_deny(&self) -> bool {
+        let spec = match self.spec {
+            Some(ref spec) => spec,
+            None => return false,
+        };
+        let has_ingress_rules = spec
+            .ingress
+            .as_ref()
+            .map(|rules| !rules.is_empty())
+            .unwrap_or(false);
+
+        spec.pod_selector.is_empty() && self.applies_to_ingress() && !has_ingress_rules
+    }
+
+    fn selects(&self, pod_labels: Option<&BTreeMap<String, String>>) -> bool {
+        self.spec
+            .as_ref()
+            .map(|spec| spec.pod_selector.matches(pod_labels))
+            .unwrap_or(false)
+    }
+}
+
+/// Returns all the network policies defined on the given namespace
+pub(crate) fn namespace_network_policies<'a>(
+    repository: &'a dyn ObjectRepository,
+    namespace: Option<&'a String>,
+) -> impl Iterator<Item = &'a NetworkPolicy> + 'a {
+    repository.iter().filter_map(move |object| match object {
+        KubeObjectType::NetworkingV1NetworkPolicy(policy)
+            if f!(policy.metadata, namespace) == namespace =>
+        {
+            Some(policy.as_ref())
+        }
+        _ => None,
+    })
+}
@@ -0,0 +1,10 @@
+use crate::f;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+/// Checks if the object is managed by a controller (like the replica sets of a deployment). Those
+/// objects are usually already accounted through its owner.
+pub(crate) fn is_controlled(meta: Option<&ObjectMeta>) -> bool {
+    f!(meta, owner_references)
+        .map(|owners| owners.iter().any(|owner| owner.controller == Some(true)))
+        .unwrap_or(false)
+}
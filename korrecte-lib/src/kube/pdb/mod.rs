@@ -0,0 +1,171 @@
+use crate::kube::label_selector::LabelSelectorExt;
+use crate::kube::owner::is_controlled;
+use crate::kube::repository::ObjectRepository;
+use crate::linters::KubeObjectType;
+use crate::{f, m};
+use k8s_openapi::api::core::v1::PodTemplateSpec;
+use k8s_openapi::api::policy::v1beta1::PodDisruptionBudget;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use std::collections::BTreeMap;
+
+/// Pod controller whose pods may be protected by a PDB
+pub(crate) struct PdbTarget<'a> {
+    pub kind: &'static str,
+    pub meta: Option<&'a ObjectMeta>,
+    pub replicas: i32,
+    pub pod_labels: Option<&'a BTreeMap<String, String>>,
+}
+
+impl<'a> PdbTarget<'a> {
+    fn new(
+        kind: &'static str,
+        meta: Option<&'a ObjectMeta>,
+        replicas: Option<&i32>,
+        template: Option<&'a PodTemplateSpec>,
+    ) -> Self {
+        PdbTarget {
+            kind,
+            meta,
+            replicas: replicas.cloned().unwrap_or(1),
+            pod_labels: template.and_then(|t| f!(t.metadata, labels)),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        f!(self.meta, name).map(|n| n.as_str()).unwrap_or("")
+    }
+
+    pub fn namespace(&self) -> Option<&String> {
+        f!(self.meta, namespace)
+    }
+
+    /// Identifies the target with the format `Kind/name`
+    pub fn reference(&self) -> String {
+        format!("{}/{}", self.kind, self.name())
+    }
+}
+
+/// Checks if the PDB selects the pods with the given labels living on the given namespace.
+/// On `policy/v1beta1`, a PDB with an empty selector does not select any pod.
+pub(crate) fn pdb_selects(
+    pdb: &PodDisruptionBudget,
+    namespace: Option<&String>,
+    pod_labels: Option<&BTreeMap<String, String>>,
+) -> bool {
+    let selector = match f!(pdb.spec, selector) {
+        Some(selector) if !selector.is_empty() => selector,
+        _ => return false,
+    };
+
+    f!(pdb.metadata, namespace) == namespace && selector.matches(pod_labels)
+}
+
+/// Returns all the PDBs which select the pods with the given labels living on the given namespace
+pub(crate) fn matching_pdbs<'a>(
+    repository: &'a dyn ObjectRepository,
+    namespace: Option<&'a String>,
+    pod_labels: Option<&'a BTreeMap<String, String>>,
+) -> impl Iterator<Item = &'a PodDisruptionBudget> + 'a {
+    repository.iter().filter_map(move |object| match object {
+        KubeObjectType::PolicyV1beta1PodDisruptionBudget(pdb)
+            if pdb_selects(pdb, namespace, pod_labels) =>
+        {
+            Some(pdb.as_ref())
+        }
+        _ => None,
+    })
+}
+
+/// Returns all the deployments, stateful sets and replica sets which are not managed by another
+/// controller
+pub(crate) fn pdb_targets(repository: &dyn ObjectRepository) -> Vec<PdbTarget<'_>> {
+    repository
+        .iter()
+        .filter(|object| !is_controlled(object.metadata()))
+        .filter_map(|object| match object {
+            KubeObjectType::AppsV1Deployment(o) => Some(PdbTarget::new(
+                "Deployment",
+                o.metadata.as_ref(),
+                f!(o.spec, replicas),
+                m!(o.spec, template),
+            )),
+            KubeObjectType::AppsV1StatefulSet(o) => Some(PdbTarget::new(
+                "StatefulSet",
+                o.metadata.as_ref(),
+                f!(o.spec, replicas),
+                m!(o.spec, template),
+            )),
+            KubeObjectType::AppsV1ReplicaSet(o) => Some(PdbTarget::new(
+                "ReplicaSet",
+                o.metadata.as_ref(),
+                f!(o.spec, replicas),
+                f!(o.spec, template),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Computes the amount of pods that the PDB requires to keep available when the target runs
+/// the given amount of replicas. Percentages are rounded up, as the disruption controller does.
+pub(crate) fn desired_healthy(pdb: &PodDisruptionBudget, replicas: i32) -> Option<i32> {
+    if let Some(min_available) = f!(pdb.spec, min_available) {
+        return scaled_value(min_available, replicas);
+    }
+
+    f!(pdb.spec, max_unavailable)
+        .and_then(|max_unavailable| scaled_value(max_unavailable, replicas))
+        .map(|max_unavailable| (replicas - max_unavailable).max(0))
+}
+
+fn scaled_value(value: &IntOrString, total: i32) -> Option<i32> {
+    match value {
+        IntOrString::Int(amount) => Some(*amount),
+        IntOrString::String(percent) => {
+            let percent = percent.strip_suffix('%')?.parse::<i32>().ok()?;
+
+            Some((percent * total + 99) / 100)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::desired_healthy;
+    use k8s_openapi::api::policy::v1beta1::{PodDisruptionBudget, PodDisruptionBudgetSpec};
+    use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+
+    #[test]
+    fn it_computes_desired_healthy_pods() {
+        let min_available = pdb(Some(IntOrString::Int(2)), None);
+        assert_eq!(Some(2), desired_healthy(&min_available, 5));
+
+        let min_available_percent = pdb(Some(IntOrString::String("50%".to_string())), None);
+        assert_eq!(Some(3), desired_healthy(&min_available_percent, 5));
+
+        let max_unavailable = pdb(None, Some(IntOrString::Int(1)));
+        assert_eq!(Some(4), desired_healthy(&max_unavailable, 5));
+
+        let max_unavailable_percent = pdb(None, Some(IntOrString::String("30%".to_string())));
+        assert_eq!(Some(3), desired_healthy(&max_unavailable_percent, 5));
+
+        let invalid = pdb(Some(IntOrString::String("half".to_string())), None);
+        assert_eq!(None, desired_healthy(&invalid, 5));
+        assert_eq!(None, desired_healthy(&pdb(None, None), 5));
+    }
+
+    fn pdb(
+        min_available: Option<IntOrString>,
+        max_unavailable: Option<IntOrString>,
+    ) -> PodDisruptionBudget {
+        PodDisruptionBudget {
+            spec: Some(PodDisruptionBudgetSpec {
+                min_available,
+                max_unavailable,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+
+const BINARY_SUFFIXES: [(&str, i32); 6] = [
+    ("Ki", 10),
+    ("Mi", 20),
+    ("Gi", 30),
+    ("Ti", 40),
+    ("Pi", 50),
+    ("Ei", 60),
+];
+
+const DECIMAL_SUFFIXES: [(&str, i32); 9] = [
+    ("n", -9),
+    ("u", -6),
+    ("m", -3),
+    ("k", 3),
+    ("M", 6),
+    ("G", 9),
+    ("T", 12),
+    ("P", 15),
+    ("E", 18),
+];
+
+pub(crate) trait QuantityExt {
+    /// Parses the quantity into its value expressed on the base unit of the resource (cores for
+    /// cpu, bytes for memory and storage). Returns `None` if the quantity is not well formed.
+    fn value(&self) -> Option<f64>;
+}
+
+impl QuantityExt for Quantity {
+    fn value(&self) -> Option<f64> {
+        parse_quantity(&self.0)
+    }
+}
+
+pub(crate) fn parse_quantity(quantity: &str) -> Option<f64> {
+    let quantity = quantity.trim();
+
+    for (suffix, exponent) in BINARY_SUFFIXES.iter() {
+        if let Some(number) = quantity.strip_suffix(suffix) {
+            return parse_number(number).map(|n| n * 2f64.powi(*exponent));
+        }
+    }
+
+    for (suffix, exponent) in DECIMAL_SUFFIXES.iter() {
+        if let Some(number) = quantity.strip_suffix(suffix) {
+            return parse_number(number).map(|n| scale(n, *exponent));
+        }
+    }
+
+    // Decimal exponent notation (`1e3`, `1E3`) is handled by the float parser
+    parse_number(quantity)
+}
+
+// Negative exponents divide by the power of ten, so values like `500m` are exactly represented
+fn scale(number: f64, exponent: i32) -> f64 {
+    if exponent < 0 {
+        number / 10f64.powi(-exponent)
+    } else {
+        number * 10f64.powi(exponent)
+    }
+}
+
+fn parse_number(number: &str) -> Option<f64> {
+    let is_valid = !number.is_empty()
+        && number
+            .chars()
+            .all(|c| c.is_ascii_digit() || "+-.eE".contains(c));
+
+    if !is_valid {
+        return None;
+    }
+
+    number.parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_quantity;
+
+    #[test]
+    fn it_parses_suffixed_quantities() {
+        assert_eq!(Some(0.5), parse_quantity("500m"));
+        assert_eq!(Some(2.0), parse_quantity("2"));
+        assert_eq!(Some(1024.0), parse_quantity("1Ki"));
+        assert_eq!(Some(268_435_456.0), parse_quantity("256Mi"));
+        assert_eq!(Some(1_500_000_000.0), parse_quantity("1.5G"));
+        assert_eq!(Some(1000.0), parse_quantity("1e3"));
+        assert_eq!(Some(0.000_1), parse_quantity("100u"));
+    }
+
+    #[test]
+    fn it_rejects_malformed_quantities() {
+        assert_eq!(None, parse_quantity(""));
+        assert_eq!(None, parse_quantity("Mi"));
+        assert_eq!(None, parse_quantity("1 GB"));
+        assert_eq!(None, parse_quantity("one"));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::f;
+use crate::kube::repository::ObjectRepository;
+use crate::linters::KubeObjectType;
+use k8s_openapi::api::rbac::v1::{PolicyRule, RoleRef, Subject};
+
+const WILDCARD: &str = "*";
+
+pub(crate) trait PolicyRuleExt {
+    /// Checks if the rule grants the given verb over the resource, taking wildcards into account
+    fn allows(&self, verb: &str, api_group: &str, resource: &str) -> bool;
+    /// Returns the name of the fields (verbs, resources or api groups) which contains a wildcard
+    fn wildcard_fields(&self) -> Vec<&'static str>;
+}
+
+impl PolicyRuleExt for PolicyRule {
+    fn allows(&self, verb: &str, api_group: &str, resource: &str) -> bool {
+        let empty = Vec::new();
+        let contains = |list: &[String], value: &str| {
+            list.iter().any(|item| item == value || item == WILDCARD)
+        };
+
+        contains(&self.verbs, verb)
+            && contains(self.api_groups.as_ref().unwrap_or(&empty), api_group)
+            && contains(self.resources.as_ref().unwrap_or(&empty), resource)
+    }
+
+    fn wildcard_fields(&self) -> Vec<&'static str> {
+        let has_wildcard = |list: Option<&Vec<String>>| {
+            list.map(|l| l.iter().any(|item| item == WILDCARD))
+                .unwrap_or(false)
+        };
+        let mut fields = Vec::new();
+
+        if has_wildcard(Some(&self.verbs)) {
+            fields.push("verbs");
+        }
+        if has_wildcard(self.resources.as_ref()) {
+            fields.push("resources");
+        }
+        if has_wildcard(self.api_groups.as_ref()) {
+            fields.push("api_groups");
+        }
+
+        fields
+    }
+}
+
+/// Subjects bound to a role, split by the kind of binding granting the access
+#[derive(Default)]
+pub(crate) struct RoleSubjects {
+    /// Subjects bound through cluster role bindings, with access on all the namespaces
+    pub(crate) cluster: Vec<String>,
+    /// Subjects bound through role bindings, with access only on the namespace of the binding.
+    /// They are formatted as `subject (namespace)`
+    pub(crate) namespaced: Vec<String>,
+}
+
+/// Finds all the subjects which are bound, either through role bindings or cluster role bindings,
+/// to the role with the given kind, name and namespace. Subjects are formatted as `Kind:name`,
+/// and service accounts include their namespace (`ServiceAccount:namespace/name`).
+pub(crate) fn bound_subjects(
+    repository: &dyn ObjectRepository,
+    role_kind: &str,
+    role_name: &str,
+    role_namespace: Option<&String>,
+) -> Vec<String> {
+    let mut subjects = Vec::new();
+    for_each_binding(
+        repository,
+        role_kind,
+        role_name,
+        role_namespace,
+        |subject, _| subjects.push(subject),
+    );
+
+    subjects.sort();
+    subjects.dedup();
+    subjects
+}
+
+/// Finds the subjects bound to the given cluster role, keeping apart the ones bound cluster-wide
+/// from the ones bound on a single namespace
+pub(crate) fn cluster_role_subjects(
+    repository: &dyn ObjectRepository,
+    role_name: &str,
+) -> RoleSubjects {
+    let mut subjects = RoleSubjects::default();
+    for_each_binding(
+        repository,
+        "ClusterRole",
+        role_name,
+        None,
+        |subject, binding_namespace| match binding_namespace {
+            Some(ns) => subjects.namespaced.push(format!("{} ({})", subject, ns)),
+            None => subjects.cluster.push(subject),
+        },
+    );
+
+    for list in [&mut subjects.cluster, &mut subjects.namespaced].iter_mut() {
+        list.sort();
+        list.dedup();
+    }
+    subjects
+}
+
+/// Calls `f` with every subject bound to the given role and the namespace of the role binding,
+/// which is `None` for cluster role bindings
+fn for_each_binding<F>(
+    repository: &dyn ObjectRepository,
+    role_kind: &str,
+    role_name: &str,
+    role_namespace: Option<&String>,
+    mut f: F,
+) where
+    F: FnMut(String, Option<&String>),
+{
+    let references_role =
+        |role_ref: &RoleRef| role_ref.kind == role_kind && role_ref.name == role_name;
+
+    for object in repository.iter() {
+        match object {
+            KubeObjectType::RbacV1RoleBinding(binding) if references_role(&binding.role_ref) => {
+                let binding_namespace = f!(binding.metadata, namespace);
+
+                // Roles can only be bound from its own namespace
+                if role_kind == "Role" && binding_namespace != role_namespace {
+                    continue;
+                }
+
+                for subject in binding.subjects.iter().flatten() {
+                    f(
+                        format_subject(subject, binding_namespace),
+                        binding_namespace,
+                    );
+                }
+            }
+            KubeObjectType::RbacV1ClusterRoleBinding(binding)
+                if role_kind == "ClusterRole" && references_role(&binding.role_ref) =>
+            {
+                for subject in binding.subjects.iter().flatten() {
+                    f(format_subject(subject, None), None);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn format_subject(subject: &Subject, binding_namespace: Option<&String>) -> String {
+    if subject.kind == "ServiceAccount" {
+        let namespace = subject
+            .namespace
+            .as_ref()
+            .or(binding_namespace)
+            .map(|ns| ns.as_str())
+            .unwrap_or("default");
+
+        return format!("{}:{}/{}", subject.kind, namespace, subject.name);
+    }
+
+    format!("{}:{}", subject.kind, subject.name)
+}
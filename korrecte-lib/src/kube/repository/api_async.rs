@@ -3,8 +3,11 @@ use crate::linters::KubeObjectType;
 use anyhow::*;
 use futures::future::Future;
 use futures::FutureExt;
+use k8s_openapi::apimachinery::pkg::version::Info;
+use k8s_openapi::http;
 use kube::api::{ListParams, Meta, Resource};
 use kube::runtime::Reflector;
+use log::*;
 use serde::de::DeserializeOwned;
 use std::pin::Pin;
 
@@ -12,8 +15,15 @@ pub struct ApiObjectRepository {
     kubeclient: kube::client::APIClient,
 }
 
+/// Objects loaded from the cluster. Kinds which could not be listed, like the ones the user has
+/// no access to, are missing from `objects` and listed on `failed_kinds`.
+pub struct ClusterObjects {
+    pub objects: Vec<KubeObjectType>,
+    pub failed_kinds: Vec<String>,
+}
+
 type ReflectorFuture<'a> =
-    Box<dyn Future<Output = Result<Vec<KubeObjectType>, anyhow::Error>> + 'a>;
+    Box<dyn Future<Output = (&'static str, Result<Vec<KubeObjectType>, anyhow::Error>)> + 'a>;
 
 impl ApiObjectRepository {
     pub fn new() -> Result<Self> {
@@ -22,10 +32,15 @@ impl ApiObjectRepository {
         Ok(Self { kubeclient })
     }
 
-    pub async fn load_all_objects(&self) -> Result<Vec<KubeObjectType>, ()> {
+    pub async fn load_all_objects(&self) -> ClusterObjects {
         let mut v: Vec<Pin<ReflectorFuture>> = Vec::new();
         let mut objects = Vec::new();
+        let mut failed_kinds = Vec::new();
 
+        v.push(
+            self.reflector_for::<k8s_openapi::api::core::v1::Namespace>("CoreV1Namespace")
+                .boxed(),
+        );
         v.push(
             self.reflector_for::<k8s_openapi::api::core::v1::Node>("CoreV1Node")
                 .boxed(),
@@ -38,6 +53,22 @@ impl ApiObjectRepository {
             self.reflector_for::<k8s_openapi::api::core::v1::Service>("CoreV1Service")
                 .boxed(),
         );
+        v.push(
+            self.reflector_for::<k8s_openapi::api::core::v1::Secret>("CoreV1Secret")
+                .boxed(),
+        );
+        v.push(
+            self.reflector_for::<k8s_openapi::api::core::v1::ConfigMap>("CoreV1ConfigMap")
+                .boxed(),
+        );
+        v.push(
+            self.reflector_for::<k8s_openapi::api::core::v1::LimitRange>("CoreV1LimitRange")
+                .boxed(),
+        );
+        v.push(
+            self.reflector_for::<k8s_openapi::api::core::v1::ResourceQuota>("CoreV1ResourceQuota")
+                .boxed(),
+        );
         v.push(
             self.reflector_for::<k8s_openapi::api::apps::v1::DaemonSet>("AppsV1DaemonSet")
                 .boxed(),
@@ -84,6 +115,12 @@ impl ApiObjectRepository {
             )
             .boxed(),
         );
+        v.push(
+            self.reflector_for::<k8s_openapi::api::networking::v1::NetworkPolicy>(
+                "NetworkingV1NetworkPolicy",
+            )
+            .boxed(),
+        );
         v.push(
             self.reflector_for::<k8s_openapi::api::extensions::v1beta1::Ingress>(
                 "ExtensionsV1beta1Ingress",
@@ -98,34 +135,73 @@ impl ApiObjectRepository {
             self.reflector_for::<k8s_openapi::api::rbac::v1::Role>("RbacV1Role")
                 .boxed(),
         );
+        v.push(
+            self.reflector_for::<k8s_openapi::api::rbac::v1::ClusterRoleBinding>(
+                "RbacV1ClusterRoleBinding",
+            )
+            .boxed(),
+        );
+        v.push(
+            self.reflector_for::<k8s_openapi::api::rbac::v1::RoleBinding>("RbacV1RoleBinding")
+                .boxed(),
+        );
+        v.push(
+            self.reflector_for::<k8s_openapi::api::storage::v1::StorageClass>(
+                "StorageV1StorageClass",
+            )
+            .boxed(),
+        );
 
-        let all_futures: Vec<Result<Vec<KubeObjectType>, anyhow::Error>> =
+        let all_futures: Vec<(&'static str, Result<Vec<KubeObjectType>, anyhow::Error>)> =
             futures::future::join_all(v).await;
 
-        for f in all_futures {
+        for (ty, f) in all_futures {
             match f {
-                Err(ref e) => println!("Error loading some resource: {}", e),
+                Err(ref e) => {
+                    warn!("{}", e);
+                    failed_kinds.push(ty.to_string());
+                }
                 Ok(current) => objects.extend(current),
             }
         }
 
-        Ok(objects)
+        ClusterObjects {
+            objects,
+            failed_kinds,
+        }
+    }
+
+    /// Version of the API server
+    pub async fn server_version(&self) -> Result<Info> {
+        let request = http::Request::get("/version").body(Vec::new())?;
+
+        Ok(self.kubeclient.request::<Info>(request).await?)
     }
 
     pub async fn reflector_for<R: ReflectorFor>(
         &self,
         ty: &'static str,
-    ) -> Result<Vec<KubeObjectType>, anyhow::Error> {
+    ) -> (&'static str, Result<Vec<KubeObjectType>, anyhow::Error>) {
+        let objects = self
+            .list::<R>()
+            .await
+            .map_err(|e| anyhow!("Err loading {}: {}", ty, e));
+
+        (ty, objects)
+    }
+
+    async fn list<R: ReflectorFor>(&self) -> Result<Vec<KubeObjectType>, anyhow::Error> {
         let client = self.kubeclient.clone();
 
         let reflector = Reflector::<R>::new(client, ListParams::default(), Resource::all::<R>());
         let reflector = reflector.init().await?;
 
-        reflector
+        Ok(reflector
             .state()
-            .await
-            .map(|objects| objects.iter().map(|obj| obj.clone().into()).collect())
-            .map_err(|e| anyhow!("Err loading {}: {}", ty, e))
+            .await?
+            .iter()
+            .map(|obj| obj.clone().into())
+            .collect())
     }
 }
 
@@ -139,10 +215,10 @@ pub struct FrozenObjectRepository {
 impl From<ApiObjectRepository> for FrozenObjectRepository {
     fn from(api: ApiObjectRepository) -> Self {
         let mut rt = tokio::runtime::Runtime::new().unwrap();
-        let all_objects = rt.block_on(api.load_all_objects()).unwrap();
+        let all_objects = rt.block_on(api.load_all_objects());
 
         FrozenObjectRepository {
-            objects: all_objects,
+            objects: all_objects.objects,
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::kube::hpa::expose_container_resource_metrics;
 use crate::linters::KubeObjectType;
 use anyhow::{anyhow, Result};
 use log::*;
@@ -34,25 +35,27 @@ impl KubeObjectLoader {
     }
 
     fn yaml_object_to_kube_object_type(yaml: &Yaml) -> Result<KubeObjectType> {
+        let hash = yaml
+            .as_hash()
+            .ok_or_else(|| anyhow!("Exepected a hash object on the root"))?;
+        let api_version = hash
+            .get(&Yaml::String("apiVersion".to_string()))
+            .and_then(|api_version| api_version.as_str())
+            .ok_or_else(|| anyhow!("Could not find apiVersion field"))?;
+        let kind = hash
+            .get(&Yaml::String("kind".to_string()))
+            .and_then(|kind| kind.as_str())
+            .ok_or_else(|| anyhow!("Could not find kind field"))?;
+
         let mut out_str = String::new();
-        let (api_version, kind) = {
-            let mut emitter = YamlEmitter::new(&mut out_str);
-            emitter.dump(&yaml)?;
-
-            let hash = yaml
-                .as_hash()
-                .ok_or_else(|| anyhow!("Exepected a hash object on the root"))?;
-            let api_version = hash
-                .get(&Yaml::String("apiVersion".to_string()))
-                .and_then(|api_version| api_version.as_str())
-                .ok_or_else(|| anyhow!("Could not find apiVersion field"))?;
-            let kind = hash
-                .get(&Yaml::String("kind".to_string()))
-                .and_then(|kind| kind.as_str())
-                .ok_or_else(|| anyhow!("Could not find kind field"))?;
-
-            (api_version, kind)
-        };
+        let mut emitter = YamlEmitter::new(&mut out_str);
+        if kind == "HorizontalPodAutoscaler" {
+            let mut hpa = yaml.clone();
+            expose_container_resource_metrics(&mut hpa);
+            emitter.dump(&hpa)?;
+        } else {
+            emitter.dump(yaml)?;
+        }
 
         KubeObjectType::from_yaml(&out_str, api_version, kind)
     }
@@ -1,7 +1,9 @@
 use crate::kube::repository::file::KubeObjectLoader;
 use crate::kube::repository::ObjectRepository;
 use crate::linters::KubeObjectType;
+use crate::snapshot::Snapshot;
 use anyhow::Result;
+use log::*;
 use std::path::Path;
 
 pub struct FileObjectRepository {
@@ -10,6 +12,24 @@ pub struct FileObjectRepository {
 
 impl FileObjectRepository {
     pub fn new(path: &Path) -> Result<FileObjectRepository> {
+        if Snapshot::is_snapshot(path) {
+            let snapshot = Snapshot::read(path)?;
+            info!(
+                "Loaded snapshot captured at {} with {} objects",
+                snapshot.metadata.captured_at.0, snapshot.metadata.objects
+            );
+            if !snapshot.metadata.failed_kinds.is_empty() {
+                warn!(
+                    "The snapshot is incomplete, these kinds could not be captured: {}",
+                    snapshot.metadata.failed_kinds.join(", ")
+                );
+            }
+
+            return Ok(FileObjectRepository {
+                objects: snapshot.objects,
+            });
+        }
+
         let objects = if path.is_dir() {
             let objects: Vec<Result<KubeObjectType>> = path
                 .read_dir()?
@@ -1,5 +1,8 @@
+use crate::f;
+use crate::kube::repository::ObjectRepository;
+use crate::linters::KubeObjectType;
 use crate::visitor::PodSpecVisitor;
-use k8s_openapi::api::core::v1::PodSpec;
+use k8s_openapi::api::core::v1::{PodSpec, Service};
 use kube::api::ObjectMeta;
 use std::collections::BTreeMap;
 
@@ -43,3 +46,85 @@ impl<'a> PodSpecVisitor for FindFistMatchingPodSpec<'a> {
         }
     }
 }
+
+/// Collects all the pod specs which match with the given selector. Unlike
+/// `FindFistMatchingPodSpec`, only pod specs on the given namespace are considered.
+pub(crate) struct FindAllMatchingPodSpecs<'a> {
+    selector: &'a BTreeMap<String, String>,
+    namespace: Option<&'a String>,
+    matching_pod_specs: Vec<MatchingPodSpec>,
+}
+
+pub(crate) struct MatchingPodSpec {
+    pub pod_spec: PodSpec,
+    pub owner: Option<String>,
+}
+
+impl<'a> FindAllMatchingPodSpecs<'a> {
+    pub fn new(selector: &'a BTreeMap<String, String>, namespace: Option<&'a String>) -> Self {
+        FindAllMatchingPodSpecs {
+            selector,
+            namespace,
+            matching_pod_specs: Vec::new(),
+        }
+    }
+
+    pub fn matching_pod_specs(&self) -> &[MatchingPodSpec] {
+        &self.matching_pod_specs
+    }
+}
+
+impl<'a> PodSpecVisitor for FindAllMatchingPodSpecs<'a> {
+    fn visit_pod_spec(&mut self, pod: &PodSpec, pod_meta: &ObjectMeta, meta: Option<&ObjectMeta>) {
+        if meta.and_then(|m| m.namespace.as_ref()) != self.namespace {
+            return;
+        }
+
+        if let Some(pod_labels) = pod_meta.labels.as_ref() {
+            let has_matching_labels = self.selector.iter().all(|(k, v)| {
+                pod_labels
+                    .get(k)
+                    .map(|pod_value| pod_value == v)
+                    .unwrap_or(false)
+            });
+
+            if has_matching_labels {
+                self.matching_pod_specs.push(MatchingPodSpec {
+                    pod_spec: pod.clone(),
+                    owner: meta.and_then(|m| m.name.clone()),
+                });
+            }
+        }
+    }
+}
+
+/// Returns the services which select the pods with the given labels living on the given namespace.
+/// Services without selector are skipped, as their endpoints are managed manually.
+pub(crate) fn selecting_services<'a>(
+    repository: &'a dyn ObjectRepository,
+    namespace: Option<&'a String>,
+    pod_labels: Option<&'a BTreeMap<String, String>>,
+) -> impl Iterator<Item = &'a Service> + 'a {
+    repository.iter().filter_map(move |object| match object {
+        KubeObjectType::CoreV1Service(service) if selects(service, namespace, pod_labels) => {
+            Some(service.as_ref())
+        }
+        _ => None,
+    })
+}
+
+fn selects(
+    service: &Service,
+    namespace: Option<&String>,
+    pod_labels: Option<&BTreeMap<String, String>>,
+) -> bool {
+    let selector = match f!(service.spec, selector) {
+        Some(selector) if !selector.is_empty() => selector,
+        _ => return false,
+    };
+
+    f!(service.metadata, namespace) == namespace
+        && pod_labels
+            .map(|labels| selector.iter().all(|(k, v)| labels.get(k) == Some(v)))
+            .unwrap_or(false)
+}
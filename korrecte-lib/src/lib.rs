@@ -1,9 +1,11 @@
 pub mod config;
 pub mod executor;
+pub mod fix;
 pub mod kube;
 pub mod linters;
 pub(crate) mod macros;
 pub mod reporting;
+pub mod snapshot;
 mod visitor;
 
 #[cfg(test)]
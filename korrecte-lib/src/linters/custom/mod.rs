@@ -0,0 +1,371 @@
+use crate::f;
+use crate::kube::namespace::namespace_matches;
+use crate::linters::evaluator::Context;
+use crate::linters::{Group, KubeObjectType, Lint, LintSpec, Severity};
+use crate::reporting::Finding;
+use anyhow::{Context as _, Result};
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path as FsPath;
+
+mod path;
+
+use path::Path;
+
+/// Rules defined by the user. Rules can be declared inline on the configuration or on YAML files
+/// with a `rules` list.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub(crate) struct Config {
+    #[serde(default)]
+    files: Vec<String>,
+    #[serde(default)]
+    rules: Vec<CustomRule>,
+}
+
+impl Config {
+    /// Loads the rules declared on the configured files
+    pub(crate) fn load_files(&mut self) -> Result<()> {
+        for file in self.files.iter() {
+            let rules = load_rules(FsPath::new(file))
+                .with_context(|| format!("Could not load custom rules from {}", file))?;
+            self.rules.extend(rules);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn rules(&self) -> &[CustomRule] {
+        &self.rules
+    }
+}
+
+#[derive(Deserialize)]
+struct RulesFile {
+    rules: Vec<CustomRule>,
+}
+
+fn load_rules(path: &FsPath) -> Result<Vec<CustomRule>> {
+    let mut file = File::open(path)?;
+    let mut buffer = String::new();
+    file.read_to_string(&mut buffer)?;
+    let rules_file: RulesFile = serde_yaml::from_str(&buffer)?;
+
+    Ok(rules_file.rules)
+}
+
+/// Declarative lint: objects selected by `match` are reported when any of the assertions fails
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub(crate) struct CustomRule {
+    name: String,
+    group: Group,
+    #[serde(default)]
+    severity: Severity,
+    description: String,
+    #[serde(default)]
+    references: Vec<String>,
+    #[serde(default, rename = "match")]
+    matcher: Matcher,
+    #[serde(rename = "assert")]
+    assertions: Vec<Assertion>,
+}
+
+impl CustomRule {
+    pub fn spec(&self) -> LintSpec {
+        LintSpec {
+            group: self.group.clone(),
+            name: self.name.clone(),
+            severity: self.severity.clone(),
+            description: self.description.clone(),
+            references: self.references.clone(),
+        }
+    }
+}
+
+/// Selects the objects checked by a rule. Empty fields match all the objects.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+struct Matcher {
+    #[serde(default)]
+    kinds: Vec<String>,
+    /// Glob patterns of the namespaces
+    #[serde(default)]
+    namespaces: Vec<String>,
+    #[serde(default)]
+    labels: BTreeMap<String, String>,
+}
+
+impl Matcher {
+    fn matches(&self, object: &KubeObjectType) -> bool {
+        let meta = object.metadata();
+        let labels = f!(meta, labels);
+        let has_labels = self
+            .labels
+            .iter()
+            .all(|(k, v)| labels.and_then(|labels| labels.get(k)) == Some(v));
+
+        (self.kinds.is_empty() || self.kinds.iter().any(|k| k == object.kind()))
+            && namespace_matches(&self.namespaces, f!(meta, namespace))
+            && has_labels
+    }
+}
+
+/// Conditions over the values selected by `path`. All the given conditions have to hold on every
+/// selected value. Conditions other than `exists` are not evaluated when nothing is selected.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct Assertion {
+    path: Path,
+    exists: Option<bool>,
+    equals: Option<Value>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_regex",
+        serialize_with = "serialize_regex"
+    )]
+    regex: Option<Regex>,
+    gt: Option<f64>,
+    gte: Option<f64>,
+    lt: Option<f64>,
+    lte: Option<f64>,
+    #[serde(rename = "in")]
+    one_of: Option<Vec<Value>>,
+}
+
+/// Numeric condition along with its threshold and the comparison the value has to satisfy
+type Comparison = (&'static str, Option<f64>, fn(f64, f64) -> bool);
+
+/// Condition which did not hold, along with the value which broke it (if any)
+struct Failure<'a> {
+    condition: &'static str,
+    value: Option<&'a Value>,
+}
+
+impl Assertion {
+    fn evaluate<'a>(&self, object: &'a Value) -> Option<Failure<'a>> {
+        let values = self.path.select(object);
+
+        match self.exists {
+            Some(true) if values.is_empty() => return Some(Failure::missing()),
+            Some(false) if !values.is_empty() => {
+                return Some(Failure::new("exists", values[0]));
+            }
+            _ => {}
+        }
+
+        values
+            .into_iter()
+            .find_map(|value| self.failed_condition(value).map(|c| Failure::new(c, value)))
+    }
+
+    fn failed_condition(&self, value: &Value) -> Option<&'static str> {
+        if let Some(expected) = self.equals.as_ref() {
+            if value != expected {
+                return Some("equals");
+            }
+        }
+
+        if let Some(regex) = self.regex.as_ref() {
+            if !regex.is_match(&as_string(value)) {
+                return Some("regex");
+            }
+        }
+
+        let number = as_number(value);
+        let comparisons: [Comparison; 4] = [
+            ("gt", self.gt, |v, t| v > t),
+            ("gte", self.gte, |v, t| v >= t),
+            ("lt", self.lt, |v, t| v < t),
+            ("lte", self.lte, |v, t| v <= t),
+        ];
+        for (condition, threshold, compare) in comparisons.iter() {
+            if let Some(threshold) = threshold {
+                if !number.map(|n| compare(n, *threshold)).unwrap_or(false) {
+                    return Some(condition);
+                }
+            }
+        }
+
+        if let Some(allowed) = self.one_of.as_ref() {
+            if !allowed.contains(value) {
+                return Some("in");
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a> Failure<'a> {
+    fn new(condition: &'static str, value: &'a Value) -> Self {
+        Failure {
+            condition,
+            value: Some(value),
+        }
+    }
+
+    fn missing() -> Self {
+        Failure {
+            condition: "exists",
+            value: None,
+        }
+    }
+}
+
+fn as_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn deserialize_regex<'de, D>(deserializer: D) -> Result<Option<Regex>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let pattern = String::deserialize(deserializer)?;
+
+    Regex::new(&pattern)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+fn serialize_regex<S>(regex: &Option<Regex>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    regex.as_ref().map(Regex::as_str).serialize(serializer)
+}
+
+pub(crate) struct CustomRuleLint {
+    rule: CustomRule,
+}
+
+impl CustomRuleLint {
+    pub fn new(rule: CustomRule) -> Self {
+        CustomRuleLint { rule }
+    }
+}
+
+impl Lint for CustomRuleLint {
+    fn name(&self) -> &str {
+        &self.rule.name
+    }
+
+    fn object(&self, object: &KubeObjectType, context: &Context) {
+        if !self.rule.matcher.matches(object) {
+            return;
+        }
+
+        let json = match object.to_json() {
+            Ok(json) => json,
+            Err(_) => return,
+        };
+
+        for assertion in self.rule.assertions.iter() {
+            if let Some(failure) = assertion.evaluate(&json) {
+                let mut finding = Finding::new(&self.rule.name, object.metadata().cloned())
+                    .add_metadata("path", &assertion.path)
+                    .add_metadata("condition", failure.condition);
+                if let Some(value) = failure.value {
+                    finding = finding.add_metadata("value", as_string(value));
+                }
+
+                context.reporter.report(finding);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Config;
+    use crate::linters::LintSpecLoader;
+    use crate::tests::{analyze_file_cfg, filter_findings_by};
+    use std::path::Path;
+
+    fn config() -> crate::config::Config {
+        let mut custom_rules: Config =
+            toml::from_str(r#"files = ["../tests/custom_rules/rules.yaml"]"#).unwrap();
+        custom_rules.load_files().unwrap();
+
+        crate::config::Config {
+            custom_rules,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_reports_failed_assertions() {
+        let findings = analyze_file_cfg(Path::new("../tests/custom_rules.yaml"), config());
+
+        let registry = filter_findings_by(findings.clone(), "trusted_registry");
+        assert_eq!(1, registry.len());
+        assert_eq!("untrusted", registry[0].name());
+        assert_eq!("regex", registry[0].lint_metadata()["condition"]);
+        assert_eq!(
+            "docker.io/library/nginx:latest",
+            registry[0].lint_metadata()["value"]
+        );
+
+        let replicas = filter_findings_by(findings.clone(), "production_replicas");
+        assert_eq!(2, replicas.len());
+        assert_eq!("untrusted", replicas[0].name());
+        assert_eq!("gte", replicas[0].lint_metadata()["condition"]);
+        assert_eq!("1", replicas[0].lint_metadata()["value"]);
+        assert_eq!("untrusted", replicas[1].name());
+        assert_eq!("exists", replicas[1].lint_metadata()["condition"]);
+        assert_eq!(
+            "metadata.annotations.owner",
+            replicas[1].lint_metadata()["path"]
+        );
+
+        let service_type = filter_findings_by(findings, "allowed_service_types");
+        assert_eq!(1, service_type.len());
+        assert_eq!("public", service_type[0].name());
+        assert_eq!("in", service_type[0].lint_metadata()["condition"]);
+        assert_eq!("LoadBalancer", service_type[0].lint_metadata()["value"]);
+    }
+
+    #[test]
+    fn custom_rules_are_listed_with_the_specs() {
+        let specs = LintSpecLoader::with_config(&config()).unwrap();
+        let spec = specs.get("trusted_registry").unwrap();
+
+        assert_eq!(
+            "Images have to be pulled from the internal registry",
+            spec.description
+        );
+        assert_eq!("error", spec.severity.to_string());
+        assert!(specs.get("required_labels").is_some());
+    }
+
+    #[test]
+    fn it_rejects_rules_with_the_name_of_a_builtin_lint() {
+        let custom_rules: Config = toml::from_str(
+            r#"
+[[rules]]
+name = "required_labels"
+group = "Audit"
+description = "Duplicated"
+assert = [{ path = "metadata.name", exists = true }]
+"#,
+        )
+        .unwrap();
+        let config = crate::config::Config {
+            custom_rules,
+            ..Default::default()
+        };
+
+        assert!(LintSpecLoader::with_config(&config).is_err());
+    }
+}
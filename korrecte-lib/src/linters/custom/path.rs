@@ -0,0 +1,206 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::fmt;
+
+/// Expression selecting fields of an object, with a syntax similar to JSONPath:
+/// `spec.template.spec.containers[*].image` or `metadata.labels["app.kubernetes.io/name"]`.
+/// The leading `$` is optional.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Path {
+    expression: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    Field(String),
+    Index(usize),
+    Wildcard,
+}
+
+impl Path {
+    pub fn parse(expression: &str) -> Result<Path> {
+        let mut segments = Vec::new();
+        let mut chars = expression.trim().chars().peekable();
+
+        if chars.peek() == Some(&'$') {
+            chars.next();
+        }
+
+        while let Some(c) = chars.next() {
+            match c {
+                '.' => {}
+                '[' => {
+                    let mut content = String::new();
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some(c) => content.push(c),
+                            None => return Err(anyhow!("Unclosed bracket on `{}`", expression)),
+                        }
+                    }
+                    segments.push(Self::parse_bracket(&content, expression)?);
+                    continue;
+                }
+                '*' => {
+                    segments.push(Segment::Wildcard);
+                    continue;
+                }
+                c => {
+                    let mut field = c.to_string();
+                    while let Some(next) = chars.peek() {
+                        if *next == '.' || *next == '[' {
+                            break;
+                        }
+                        field.push(*next);
+                        chars.next();
+                    }
+                    segments.push(Segment::Field(field));
+                    continue;
+                }
+            }
+
+            // A dot has to be followed by a field or a wildcard
+            match chars.peek() {
+                Some('.') | Some('[') | None => {
+                    return Err(anyhow!("Missing field name on `{}`", expression))
+                }
+                _ => {}
+            }
+        }
+
+        if segments.is_empty() {
+            return Err(anyhow!("Empty path expression"));
+        }
+
+        Ok(Path {
+            expression: expression.to_string(),
+            segments,
+        })
+    }
+
+    fn parse_bracket(content: &str, expression: &str) -> Result<Segment> {
+        let content = content.trim();
+
+        if content == "*" {
+            return Ok(Segment::Wildcard);
+        }
+
+        let quoted = |quote: char| {
+            content.len() >= 2 && content.starts_with(quote) && content.ends_with(quote)
+        };
+        if quoted('"') || quoted('\'') {
+            return Ok(Segment::Field(content[1..content.len() - 1].to_string()));
+        }
+
+        content
+            .parse::<usize>()
+            .map(Segment::Index)
+            .map_err(|_| anyhow!("Invalid index `{}` on `{}`", content, expression))
+    }
+
+    /// Returns all the values selected by the expression. Wildcards select all the items of an
+    /// array or all the values of a map.
+    pub fn select<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let mut current = vec![value];
+
+        for segment in self.segments.iter() {
+            current = current
+                .into_iter()
+                .flat_map(|value| match (segment, value) {
+                    (Segment::Field(name), Value::Object(map)) => {
+                        map.get(name).into_iter().collect()
+                    }
+                    (Segment::Index(index), Value::Array(items)) => {
+                        items.get(*index).into_iter().collect()
+                    }
+                    (Segment::Wildcard, Value::Array(items)) => items.iter().collect(),
+                    (Segment::Wildcard, Value::Object(map)) => map.values().collect(),
+                    _ => Vec::new(),
+                })
+                .filter(|value| !value.is_null())
+                .collect();
+        }
+
+        current
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+impl<'de> Deserialize<'de> for Path {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let expression = String::deserialize(deserializer)?;
+
+        Path::parse(&expression).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Serialize for Path {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Path;
+    use serde_json::json;
+
+    #[test]
+    fn it_selects_fields() {
+        let object = json!({
+            "metadata": {
+                "labels": {"app.kubernetes.io/name": "api", "tier": "backend"}
+            },
+            "spec": {
+                "containers": [
+                    {"name": "api", "image": "api:1.0"},
+                    {"name": "proxy", "image": "envoy:1.13"}
+                ]
+            }
+        });
+
+        let select = |expression: &str| -> Vec<String> {
+            Path::parse(expression)
+                .unwrap()
+                .select(&object)
+                .into_iter()
+                .map(|v| v.to_string())
+                .collect()
+        };
+
+        assert_eq!(
+            vec!["\"api:1.0\"", "\"envoy:1.13\""],
+            select("$.spec.containers[*].image")
+        );
+        assert_eq!(vec!["\"proxy\""], select("spec.containers[1].name"));
+        assert_eq!(
+            vec!["\"api\""],
+            select("metadata.labels[\"app.kubernetes.io/name\"]")
+        );
+        assert_eq!(vec!["\"api\"", "\"backend\""], select("metadata.labels.*"));
+        assert!(select("spec.containers[2].name").is_empty());
+        assert!(select("spec.missing").is_empty());
+    }
+
+    #[test]
+    fn it_rejects_invalid_expressions() {
+        assert!(Path::parse("").is_err());
+        assert!(Path::parse("spec..replicas").is_err());
+        assert!(Path::parse("spec.containers[0").is_err());
+        assert!(Path::parse("spec.containers[first]").is_err());
+        assert!(Path::parse("spec.").is_err());
+    }
+}
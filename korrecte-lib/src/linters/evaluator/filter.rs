@@ -0,0 +1,230 @@
+use crate::config::KorrecteConfig;
+use crate::kube::label_selector::{parse_selector, LabelSelectorExt};
+use crate::kube::namespace::glob_matches;
+use crate::linters::KubeObjectType;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
+use regex::Regex;
+
+/// Decides which objects are linted. An object matching any of the exclusion filters (the
+/// `ignored_*` ones) is never linted, even if it matches the inclusion filters. Otherwise, the
+/// object is linted if it matches all the inclusion filters which are defined.
+///
+/// Invalid patterns are reported as problems and never match any object.
+pub(crate) struct ObjectFilter {
+    allowed_namespaces: Vec<Option<NamespacePattern>>,
+    ignored_namespaces: Vec<Option<NamespacePattern>>,
+    allowed_names: Vec<Option<Regex>>,
+    ignored_names: Vec<Option<Regex>>,
+    allowed_kinds: Vec<String>,
+    ignored_kinds: Vec<String>,
+    selector: Selector,
+    problems: Vec<String>,
+}
+
+/// Namespaces are matched with glob patterns, or with regular expressions when the pattern is
+/// enclosed in slashes, like `/^team-[a-z]+$/`
+enum NamespacePattern {
+    Glob(String),
+    Regex(Regex),
+}
+
+enum Selector {
+    Any,
+    Matching(LabelSelector),
+    Invalid,
+}
+
+impl ObjectFilter {
+    pub(crate) fn new(config: &KorrecteConfig) -> Self {
+        let mut problems = Vec::new();
+
+        let mut namespaces = |patterns: &[String]| -> Vec<Option<NamespacePattern>> {
+            patterns
+                .iter()
+                .map(|pattern| match namespace_pattern(pattern) {
+                    Ok(pattern) => Some(pattern),
+                    Err(e) => {
+                        problems.push(format!("Invalid namespace pattern `{}`: {}", pattern, e));
+                        None
+                    }
+                })
+                .collect()
+        };
+        let allowed_namespaces = namespaces(&config.allowed_namespaces);
+        let ignored_namespaces = namespaces(&config.ignored_namespaces);
+
+        let mut names = |patterns: &[String]| -> Vec<Option<Regex>> {
+            patterns
+                .iter()
+                .map(|pattern| match Regex::new(pattern) {
+                    Ok(regex) => Some(regex),
+                    Err(e) => {
+                        problems.push(format!("Invalid name pattern `{}`: {}", pattern, e));
+                        None
+                    }
+                })
+                .collect()
+        };
+        let allowed_names = names(&config.allowed_names);
+        let ignored_names = names(&config.ignored_names);
+
+        let selector = match config.selector {
+            Some(ref expression) => match parse_selector(expression) {
+                Ok(selector) => Selector::Matching(selector),
+                Err(e) => {
+                    problems.push(e.to_string());
+                    Selector::Invalid
+                }
+            },
+            None => Selector::Any,
+        };
+
+        ObjectFilter {
+            allowed_namespaces,
+            ignored_namespaces,
+            allowed_names,
+            ignored_names,
+            allowed_kinds: config.allowed_kinds.clone(),
+            ignored_kinds: config.ignored_kinds.clone(),
+            selector,
+            problems,
+        }
+    }
+
+    /// Filters which could not be parsed
+    pub(crate) fn problems(&self) -> &[String] {
+        &self.problems
+    }
+
+    pub(crate) fn matches(&self, object: &KubeObjectType) -> bool {
+        let metadata = object.metadata();
+        // Namespaces are filtered by its own name
+        let namespace = match object {
+            KubeObjectType::CoreV1Namespace(_) => metadata.and_then(|m| m.name.as_ref()),
+            _ => metadata.and_then(|m| m.namespace.as_ref()),
+        }
+        .map(String::as_str)
+        .unwrap_or_default();
+        let name = metadata
+            .and_then(|m| m.name.as_ref())
+            .map(String::as_str)
+            .unwrap_or_default();
+        let kind = object.kind();
+
+        let namespace_matches = |pattern: &NamespacePattern| match pattern {
+            NamespacePattern::Glob(glob) => glob_matches(glob, namespace),
+            NamespacePattern::Regex(regex) => regex.is_match(namespace),
+        };
+        let name_matches = |regex: &Regex| regex.is_match(name);
+        let kind_matches = |k: &String| k.eq_ignore_ascii_case(kind);
+
+        let ignored = self
+            .ignored_namespaces
+            .iter()
+            .flatten()
+            .any(namespace_matches)
+            || self.ignored_names.iter().flatten().any(name_matches)
+            || self.ignored_kinds.iter().any(kind_matches);
+        if ignored {
+            return false;
+        }
+
+        let allowed_namespace = self.allowed_namespaces.is_empty()
+            || self
+                .allowed_namespaces
+                .iter()
+                .flatten()
+                .any(namespace_matches);
+        let allowed_name =
+            self.allowed_names.is_empty() || self.allowed_names.iter().flatten().any(name_matches);
+        let allowed_kind =
+            self.allowed_kinds.is_empty() || self.allowed_kinds.iter().any(kind_matches);
+        let selected = match self.selector {
+            Selector::Any => true,
+            Selector::Matching(ref selector) => {
+                selector.matches(metadata.and_then(|m| m.labels.as_ref()))
+            }
+            Selector::Invalid => false,
+        };
+
+        allowed_namespace && allowed_name && allowed_kind && selected
+    }
+}
+
+fn namespace_pattern(pattern: &str) -> Result<NamespacePattern, regex::Error> {
+    let regex = pattern
+        .strip_prefix('/')
+        .and_then(|pattern| pattern.strip_suffix('/'));
+
+    match regex {
+        Some(regex) => Ok(NamespacePattern::Regex(Regex::new(regex)?)),
+        None => Ok(NamespacePattern::Glob(pattern.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ObjectFilter;
+    use crate::config::KorrecteConfig;
+    use crate::linters::KubeObjectType;
+
+    #[test]
+    fn exclusions_win_over_inclusions() {
+        let config = KorrecteConfig {
+            allowed_namespaces: vec!["/^team-[a-z]+$/".to_string()],
+            ignored_names: vec!["-canary$".to_string()],
+            ..Default::default()
+        };
+        let filter = ObjectFilter::new(&config);
+
+        assert!(filter.matches(&pod("team-a", "web", "{app: web}")));
+        assert!(!filter.matches(&pod("team-a", "web-canary", "{app: web}")));
+        assert!(!filter.matches(&pod("team-1", "web", "{app: web}")));
+    }
+
+    #[test]
+    fn it_combines_all_the_inclusion_filters() {
+        let config = KorrecteConfig {
+            allowed_namespaces: vec!["prod-*".to_string()],
+            allowed_names: vec!["^api".to_string()],
+            allowed_kinds: vec!["pod".to_string()],
+            selector: Some("tier in (backend),!legacy".to_string()),
+            ..Default::default()
+        };
+        let filter = ObjectFilter::new(&config);
+
+        assert!(filter.matches(&pod("prod-eu", "api-1", "{tier: backend}")));
+        assert!(!filter.matches(&pod("prod-eu", "web-1", "{tier: backend}")));
+        assert!(!filter.matches(&pod("prod-eu", "api-1", "{tier: frontend}")));
+        assert!(!filter.matches(&pod("staging", "api-1", "{tier: backend}")));
+        assert!(!filter.matches(&pod("prod-eu", "api-1", "{tier: backend, legacy: old}")));
+
+        let config = KorrecteConfig {
+            allowed_kinds: vec!["Deployment".to_string()],
+            ..Default::default()
+        };
+        assert!(!ObjectFilter::new(&config).matches(&pod("prod-eu", "api-1", "{tier: backend}")));
+    }
+
+    #[test]
+    fn invalid_filters_are_reported_and_match_nothing() {
+        let config = KorrecteConfig {
+            allowed_names: vec!["(".to_string()],
+            selector: Some("tier in backend".to_string()),
+            ..Default::default()
+        };
+        let filter = ObjectFilter::new(&config);
+
+        assert_eq!(2, filter.problems().len());
+        assert!(!filter.matches(&pod("prod-eu", "api-1", "{tier: backend}")));
+    }
+
+    fn pod(namespace: &str, name: &str, labels: &str) -> KubeObjectType {
+        let yaml = format!(
+            "metadata:\n  name: {}\n  namespace: {}\n  labels: {}\nspec:\n  containers: []",
+            name, namespace, labels
+        );
+
+        KubeObjectType::from_yaml(&yaml, "v1", "Pod").unwrap()
+    }
+}
@@ -2,9 +2,12 @@ use crate::kube::repository::ObjectRepository;
 use crate::linters::LintList;
 use crate::reporting::Reporter;
 
+mod filter;
+mod profile;
 mod single_evaluator;
 
 use crate::config::KorrecteConfig;
+pub(crate) use crate::linters::evaluator::filter::ObjectFilter;
 pub use crate::linters::evaluator::single_evaluator::SingleEvaluator;
 
 #[allow(clippy::ptr_arg)]
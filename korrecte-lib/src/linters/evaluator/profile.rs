@@ -0,0 +1,168 @@
+use crate::config::{Config, Override};
+use crate::linters::evaluator::Context;
+use crate::linters::{KubeObjectType, Lint, LintCollection, LintList, Severity};
+use crate::reporting::{Finding, Reporter};
+use log::*;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// Lints and settings resolved for the objects matched by the same set of override blocks
+#[derive(Default)]
+pub(crate) struct Profile {
+    disabled: HashSet<String>,
+    severities: HashMap<String, Severity>,
+    /// Lints built with the overridden configuration. If none of the blocks configures lints,
+    /// the default ones are used.
+    lints: Option<LintList<'static>>,
+}
+
+impl Profile {
+    fn resolve(config: &Config, blocks: &[&Override]) -> Profile {
+        let mut profile = Profile::default();
+
+        for block in blocks {
+            profile.disabled.extend(block.disabled.iter().cloned());
+            for lint in &block.enabled {
+                profile.disabled.remove(lint);
+            }
+            profile.severities.extend(block.severity.clone());
+        }
+
+        if blocks.iter().any(|block| !block.config.is_empty()) {
+            match config.with_overrides(blocks) {
+                Ok(config) => profile.lints = Some(LintCollection::all(&config)),
+                Err(e) => error!("Could not apply overrides: {}", e),
+            }
+        }
+
+        profile
+    }
+
+    /// Runs the lint on the object, as configured by the overrides
+    pub(crate) fn run(&self, lint: &dyn Lint, object: &KubeObjectType, context: &Context) {
+        if self.disabled.contains(lint.name()) {
+            return;
+        }
+
+        let overridden = self
+            .lints
+            .iter()
+            .flatten()
+            .find(|overridden| overridden.name() == lint.name());
+        let lint: &dyn Lint = match overridden {
+            Some(overridden) => overridden.as_ref(),
+            None => lint,
+        };
+
+        match self.severities.get(lint.name()) {
+            Some(severity) => {
+                let reporter = SeverityReporter {
+                    reporter: context.reporter,
+                    severity,
+                };
+                let context = Context {
+                    repository: context.repository,
+                    reporter: &reporter,
+                    config: context.config,
+                };
+
+                lint.object(object, &context);
+            }
+            None => lint.object(object, context),
+        }
+    }
+}
+
+/// Profiles of the objects, resolved lazily from the overrides matching each of them
+pub(crate) struct Profiles<'a> {
+    config: &'a Config,
+    resolved: RefCell<HashMap<Vec<usize>, Rc<Profile>>>,
+}
+
+impl<'a> Profiles<'a> {
+    pub(crate) fn new(config: &'a Config) -> Self {
+        Profiles {
+            config,
+            resolved: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn get(&self, object: &KubeObjectType) -> Rc<Profile> {
+        let config = self.config;
+        let matching: Vec<usize> = config
+            .overrides
+            .iter()
+            .enumerate()
+            .filter(|(_, block)| block.matches(object))
+            .map(|(index, _)| index)
+            .collect();
+
+        self.resolved
+            .borrow_mut()
+            .entry(matching)
+            .or_insert_with_key(|matching| {
+                let blocks: Vec<&Override> = matching
+                    .iter()
+                    .map(|index| &config.overrides[*index])
+                    .collect();
+
+                Rc::new(Profile::resolve(config, &blocks))
+            })
+            .clone()
+    }
+}
+
+/// Reporter replacing the severity of the findings before forwarding them
+struct SeverityReporter<'a> {
+    reporter: &'a dyn Reporter,
+    severity: &'a Severity,
+}
+
+impl Reporter for SeverityReporter<'_> {
+    fn report(&self, finding: Finding) {
+        self.reporter
+            .report(finding.with_severity(self.severity.clone()));
+    }
+
+    fn findings(&self) -> Vec<Finding> {
+        self.reporter.findings()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Config;
+    use crate::linters::Severity;
+    use crate::tests::{analyze_file_cfg, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_applies_the_matching_overrides() {
+        let config = Config::from_toml(
+            r#"
+[required_labels]
+labels = ["app"]
+
+[[overrides]]
+namespaces = ["kube-*"]
+disabled = ["required_labels"]
+
+[[overrides]]
+selector = { matchLabels = { app = "prometheus" } }
+severity = { required_labels = "error" }
+[overrides.config.required_labels]
+labels = ["app", "team"]
+"#,
+        )
+        .unwrap();
+
+        let findings = analyze_file_cfg(Path::new("../tests/overrides.yaml"), config);
+        let findings = filter_findings_by(findings, "required_labels");
+
+        assert_eq!(1, findings.len());
+        assert_eq!("prometheus", findings[0].name());
+        assert_eq!(Some(&Severity::Error), findings[0].severity());
+        assert_eq!("[\"team\"]", findings[0].lint_metadata()["missing_labels"]);
+    }
+}
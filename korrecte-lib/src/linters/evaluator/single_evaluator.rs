@@ -1,45 +1,62 @@
-use crate::config::KorrecteConfig;
-use crate::linters::evaluator::{Context, Evaluator};
+use crate::config::Config;
+use crate::linters::evaluator::profile::Profiles;
+use crate::linters::evaluator::{Context, Evaluator, ObjectFilter};
 use crate::linters::{KubeObjectType, LintList};
 
-pub struct SingleEvaluator;
+pub struct SingleEvaluator<'a> {
+    filter: ObjectFilter,
+    profiles: Profiles<'a>,
+}
 
-impl Evaluator for SingleEvaluator {
+impl Evaluator for SingleEvaluator<'_> {
     fn evaluate<'a>(&self, context: &'a Context<'a>, list: &LintList) {
         for lint in list.iter() {
             for object in context.repository.iter() {
-                if !Self::needs_linting(context.config, object) {
+                if !self.needs_linting(object) {
                     continue;
                 }
 
-                lint.object(&object, &context);
+                self.profiles
+                    .get(object)
+                    .run(lint.as_ref(), object, context);
             }
         }
     }
 }
 
-impl SingleEvaluator {
-    fn needs_linting(config: &KorrecteConfig, object: &KubeObjectType) -> bool {
-        let namespace = object
-            .metadata()
-            .and_then(|m| m.namespace.clone())
-            .unwrap_or_else(|| "".to_string());
-
-        if !config.ignored_namespaces.is_empty() {
-            return !config.ignored_namespaces.contains(&namespace);
+impl<'a> SingleEvaluator<'a> {
+    /// Creates an evaluator which applies the overrides of the given configuration before running
+    /// each lint
+    pub fn new(config: &'a Config) -> Self {
+        SingleEvaluator {
+            filter: ObjectFilter::new(&config.korrecte),
+            profiles: Profiles::new(config),
         }
+    }
 
-        if !config.allowed_namespaces.is_empty() {
-            return config.allowed_namespaces.contains(&namespace);
+    /// Runs all the lints on a single object
+    pub(crate) fn evaluate_object(
+        &self,
+        context: &Context,
+        list: &LintList,
+        object: &KubeObjectType,
+    ) {
+        let profile = self.profiles.get(object);
+
+        for lint in list.iter() {
+            profile.run(lint.as_ref(), object, context);
         }
+    }
 
-        true
+    /// Checks if the object is selected by the filters of the configuration
+    pub(crate) fn needs_linting(&self, object: &KubeObjectType) -> bool {
+        self.filter.matches(object)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::config::KorrecteConfig;
+    use crate::config::{Config, KorrecteConfig};
     use crate::linters::evaluator::SingleEvaluator;
     use crate::linters::KubeObjectType;
 
@@ -49,7 +66,7 @@ mod tests {
         cfg.ignored_namespaces = vec!["somenamespace".to_string()];
         let object = create_obj_with_metadata();
 
-        let allowed = SingleEvaluator::needs_linting(&cfg, &object);
+        let allowed = needs_linting(cfg, &object);
 
         assert_eq!(false, allowed);
     }
@@ -60,7 +77,7 @@ mod tests {
         cfg.ignored_namespaces = vec!["not-ignored".to_string()];
         let object = create_obj_with_metadata();
 
-        let allowed = SingleEvaluator::needs_linting(&cfg, &object);
+        let allowed = needs_linting(cfg, &object);
 
         assert_eq!(true, allowed);
     }
@@ -71,7 +88,7 @@ mod tests {
         cfg.allowed_namespaces = vec!["somenamespace".to_string()];
         let object = create_obj_with_metadata();
 
-        let allowed = SingleEvaluator::needs_linting(&cfg, &object);
+        let allowed = needs_linting(cfg, &object);
 
         assert_eq!(true, allowed);
     }
@@ -82,7 +99,7 @@ mod tests {
         cfg.allowed_namespaces = vec!["accepted-namespace".to_string()];
         let object = create_obj_with_metadata();
 
-        let allowed = SingleEvaluator::needs_linting(&cfg, &object);
+        let allowed = needs_linting(cfg, &object);
 
         assert_eq!(false, allowed);
     }
@@ -92,11 +109,35 @@ mod tests {
         let cfg = KorrecteConfig::default();
         let object = create_obj_with_metadata();
 
-        let allowed = SingleEvaluator::needs_linting(&cfg, &object);
+        let allowed = needs_linting(cfg, &object);
 
         assert_eq!(true, allowed);
     }
 
+    #[test]
+    fn namespaces_are_filtered_by_its_name() {
+        let cfg = KorrecteConfig {
+            ignored_namespaces: vec!["somenamespace".to_string()],
+            ..Default::default()
+        };
+        let object =
+            KubeObjectType::from_yaml("metadata:\n  name: somenamespace", "v1", "Namespace")
+                .unwrap();
+
+        let allowed = needs_linting(cfg, &object);
+
+        assert!(!allowed);
+    }
+
+    fn needs_linting(cfg: KorrecteConfig, object: &KubeObjectType) -> bool {
+        let config = Config {
+            korrecte: cfg,
+            ..Default::default()
+        };
+
+        SingleEvaluator::new(&config).needs_linting(object)
+    }
+
     fn create_obj_with_metadata() -> KubeObjectType {
         let obj = r#"
 apiVersion: policy/v1beta1
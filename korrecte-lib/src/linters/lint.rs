@@ -4,9 +4,34 @@ use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 
 pub trait Lint {
     fn name(&self) -> &str;
+    fn core_v1_namespace(
+        &self,
+        _namespace: &k8s_openapi::api::core::v1::Namespace,
+        _context: &Context,
+    ) {
+    }
     fn core_v1_node(&self, _node: &k8s_openapi::api::core::v1::Node, _context: &Context) {}
     fn core_v1_pod(&self, _pod: &k8s_openapi::api::core::v1::Pod, _context: &Context) {}
     fn core_v1_service(&self, _service: &k8s_openapi::api::core::v1::Service, _context: &Context) {}
+    fn core_v1_secret(&self, _secret: &k8s_openapi::api::core::v1::Secret, _context: &Context) {}
+    fn core_v1_config_map(
+        &self,
+        _config_map: &k8s_openapi::api::core::v1::ConfigMap,
+        _context: &Context,
+    ) {
+    }
+    fn core_v1_limit_range(
+        &self,
+        _limit_range: &k8s_openapi::api::core::v1::LimitRange,
+        _context: &Context,
+    ) {
+    }
+    fn core_v1_resource_quota(
+        &self,
+        _resource_quota: &k8s_openapi::api::core::v1::ResourceQuota,
+        _context: &Context,
+    ) {
+    }
     fn apps_v1_daemon_set(
         &self,
         _daemon_set: &k8s_openapi::api::apps::v1::DaemonSet,
@@ -61,6 +86,12 @@ pub trait Lint {
         _context: &Context,
     ) {
     }
+    fn networking_v1_network_policy(
+        &self,
+        _network_policy: &k8s_openapi::api::networking::v1::NetworkPolicy,
+        _context: &Context,
+    ) {
+    }
     fn extensions_v1beta1_ingress(
         &self,
         _ingress: &k8s_openapi::api::extensions::v1beta1::Ingress,
@@ -74,12 +105,35 @@ pub trait Lint {
     ) {
     }
     fn rbac_v1_role(&self, _role: &k8s_openapi::api::rbac::v1::Role, _context: &Context) {}
+    fn rbac_v1_cluster_role_binding(
+        &self,
+        _cluster_role_binding: &k8s_openapi::api::rbac::v1::ClusterRoleBinding,
+        _context: &Context,
+    ) {
+    }
+    fn rbac_v1_role_binding(
+        &self,
+        _role_binding: &k8s_openapi::api::rbac::v1::RoleBinding,
+        _context: &Context,
+    ) {
+    }
+    fn storage_v1_storage_class(
+        &self,
+        _storage_class: &k8s_openapi::api::storage::v1::StorageClass,
+        _context: &Context,
+    ) {
+    }
 
     fn object(&self, object: &KubeObjectType, context: &Context) {
         match object {
+            KubeObjectType::CoreV1Namespace(ref o) => self.core_v1_namespace(o, context),
             KubeObjectType::CoreV1Node(ref o) => self.core_v1_node(o, context),
             KubeObjectType::CoreV1Pod(ref o) => self.core_v1_pod(o, context),
             KubeObjectType::CoreV1Service(ref o) => self.core_v1_service(o, context),
+            KubeObjectType::CoreV1Secret(ref o) => self.core_v1_secret(o, context),
+            KubeObjectType::CoreV1ConfigMap(ref o) => self.core_v1_config_map(o, context),
+            KubeObjectType::CoreV1LimitRange(ref o) => self.core_v1_limit_range(o, context),
+            KubeObjectType::CoreV1ResourceQuota(ref o) => self.core_v1_resource_quota(o, context),
             KubeObjectType::AppsV1DaemonSet(ref o) => self.apps_v1_daemon_set(o, context),
             KubeObjectType::AppsV1Deployment(ref o) => self.apps_v1_deployment(o, context),
             KubeObjectType::AppsV1ReplicaSet(ref o) => self.apps_v1_replica_set(o, context),
@@ -99,20 +153,35 @@ pub trait Lint {
             KubeObjectType::NetworkingV1beta1Ingress(ref o) => {
                 self.networking_v1beta1_ingress(o, context)
             }
+            KubeObjectType::NetworkingV1NetworkPolicy(ref o) => {
+                self.networking_v1_network_policy(o, context)
+            }
             KubeObjectType::ExtensionsV1beta1Ingress(ref o) => {
                 self.extensions_v1beta1_ingress(o, context)
             }
             KubeObjectType::RbacV1ClusterRole(ref o) => self.rbac_v1_cluster_role(o, context),
             KubeObjectType::RbacV1Role(ref o) => self.rbac_v1_role(o, context),
+            KubeObjectType::RbacV1ClusterRoleBinding(ref o) => {
+                self.rbac_v1_cluster_role_binding(o, context)
+            }
+            KubeObjectType::RbacV1RoleBinding(ref o) => self.rbac_v1_role_binding(o, context),
+            KubeObjectType::StorageV1StorageClass(ref o) => {
+                self.storage_v1_storage_class(o, context)
+            }
         }
     }
 }
 
 #[allow(unused)]
 pub enum KubeObjectType {
+    CoreV1Namespace(Box<k8s_openapi::api::core::v1::Namespace>),
     CoreV1Node(Box<k8s_openapi::api::core::v1::Node>),
     CoreV1Pod(Box<k8s_openapi::api::core::v1::Pod>),
     CoreV1Service(Box<k8s_openapi::api::core::v1::Service>),
+    CoreV1Secret(Box<k8s_openapi::api::core::v1::Secret>),
+    CoreV1ConfigMap(Box<k8s_openapi::api::core::v1::ConfigMap>),
+    CoreV1LimitRange(Box<k8s_openapi::api::core::v1::LimitRange>),
+    CoreV1ResourceQuota(Box<k8s_openapi::api::core::v1::ResourceQuota>),
     AppsV1DaemonSet(Box<k8s_openapi::api::apps::v1::DaemonSet>),
     AppsV1Deployment(Box<k8s_openapi::api::apps::v1::Deployment>),
     AppsV1ReplicaSet(Box<k8s_openapi::api::apps::v1::ReplicaSet>),
@@ -128,9 +197,13 @@ pub enum KubeObjectType {
         Box<k8s_openapi::api::autoscaling::v2beta2::HorizontalPodAutoscaler>,
     ),
     NetworkingV1beta1Ingress(Box<k8s_openapi::api::networking::v1beta1::Ingress>),
+    NetworkingV1NetworkPolicy(Box<k8s_openapi::api::networking::v1::NetworkPolicy>),
     ExtensionsV1beta1Ingress(Box<k8s_openapi::api::extensions::v1beta1::Ingress>),
     RbacV1ClusterRole(Box<k8s_openapi::api::rbac::v1::ClusterRole>),
     RbacV1Role(Box<k8s_openapi::api::rbac::v1::Role>),
+    RbacV1ClusterRoleBinding(Box<k8s_openapi::api::rbac::v1::ClusterRoleBinding>),
+    RbacV1RoleBinding(Box<k8s_openapi::api::rbac::v1::RoleBinding>),
+    StorageV1StorageClass(Box<k8s_openapi::api::storage::v1::StorageClass>),
 }
 
 impl KubeObjectType {
@@ -147,6 +220,12 @@ impl KubeObjectType {
         };
 
         match (ty, version, kind) {
+            ("core", "v1", "Namespace") => {
+                let object = serde_yaml::from_str(yaml)?;
+
+                Ok(KubeObjectType::CoreV1Namespace(object))
+            }
+
             ("core", "v1", "Node") => {
                 let object = serde_yaml::from_str(yaml)?;
 
@@ -165,6 +244,30 @@ impl KubeObjectType {
                 Ok(KubeObjectType::CoreV1Service(object))
             }
 
+            ("core", "v1", "Secret") => {
+                let object = serde_yaml::from_str(yaml)?;
+
+                Ok(KubeObjectType::CoreV1Secret(object))
+            }
+
+            ("core", "v1", "ConfigMap") => {
+                let object = serde_yaml::from_str(yaml)?;
+
+                Ok(KubeObjectType::CoreV1ConfigMap(object))
+            }
+
+            ("core", "v1", "LimitRange") => {
+                let object = serde_yaml::from_str(yaml)?;
+
+                Ok(KubeObjectType::CoreV1LimitRange(object))
+            }
+
+            ("core", "v1", "ResourceQuota") => {
+                let object = serde_yaml::from_str(yaml)?;
+
+                Ok(KubeObjectType::CoreV1ResourceQuota(object))
+            }
+
             ("apps", "v1", "DaemonSet") => {
                 let object = serde_yaml::from_str(yaml)?;
 
@@ -223,6 +326,12 @@ impl KubeObjectType {
                 Ok(KubeObjectType::NetworkingV1beta1Ingress(object))
             }
 
+            ("networking.k8s.io", "v1", "NetworkPolicy") => {
+                let object = serde_yaml::from_str(yaml)?;
+
+                Ok(KubeObjectType::NetworkingV1NetworkPolicy(object))
+            }
+
             ("extensions", "v1beta1", "Ingress") => {
                 let object = serde_yaml::from_str(yaml)?;
 
@@ -240,6 +349,24 @@ impl KubeObjectType {
 
                 Ok(KubeObjectType::RbacV1Role(object))
             }
+
+            ("rbac.authorization.k8s.io", "v1", "ClusterRoleBinding") => {
+                let object = serde_yaml::from_str(yaml)?;
+
+                Ok(KubeObjectType::RbacV1ClusterRoleBinding(object))
+            }
+
+            ("rbac.authorization.k8s.io", "v1", "RoleBinding") => {
+                let object = serde_yaml::from_str(yaml)?;
+
+                Ok(KubeObjectType::RbacV1RoleBinding(object))
+            }
+
+            ("storage.k8s.io", "v1", "StorageClass") => {
+                let object = serde_yaml::from_str(yaml)?;
+
+                Ok(KubeObjectType::StorageV1StorageClass(object))
+            }
             _ => Err(anyhow!("Could not decode the given object type")),
         }
     }
@@ -253,6 +380,10 @@ impl KubeObjectType {
         };
 
         match *self {
+            KubeObjectType::CoreV1Namespace(_) => {
+                ty == "core" && version == "v1" && kind == "Namespace"
+            }
+
             KubeObjectType::CoreV1Node(_) => ty == "core" && version == "v1" && kind == "Node",
 
             KubeObjectType::CoreV1Pod(_) => ty == "core" && version == "v1" && kind == "Pod",
@@ -261,6 +392,20 @@ impl KubeObjectType {
                 ty == "core" && version == "v1" && kind == "Service"
             }
 
+            KubeObjectType::CoreV1Secret(_) => ty == "core" && version == "v1" && kind == "Secret",
+
+            KubeObjectType::CoreV1ConfigMap(_) => {
+                ty == "core" && version == "v1" && kind == "ConfigMap"
+            }
+
+            KubeObjectType::CoreV1LimitRange(_) => {
+                ty == "core" && version == "v1" && kind == "LimitRange"
+            }
+
+            KubeObjectType::CoreV1ResourceQuota(_) => {
+                ty == "core" && version == "v1" && kind == "ResourceQuota"
+            }
+
             KubeObjectType::AppsV1DaemonSet(_) => {
                 ty == "apps" && version == "v1" && kind == "DaemonSet"
             }
@@ -297,6 +442,10 @@ impl KubeObjectType {
                 ty == "networking.k8s.io" && version == "v1beta1" && kind == "Ingress"
             }
 
+            KubeObjectType::NetworkingV1NetworkPolicy(_) => {
+                ty == "networking.k8s.io" && version == "v1" && kind == "NetworkPolicy"
+            }
+
             KubeObjectType::ExtensionsV1beta1Ingress(_) => {
                 ty == "extensions" && version == "v1beta1" && kind == "Ingress"
             }
@@ -308,17 +457,39 @@ impl KubeObjectType {
             KubeObjectType::RbacV1Role(_) => {
                 ty == "rbac.authorization.k8s.io" && version == "v1" && kind == "Role"
             }
+
+            KubeObjectType::RbacV1ClusterRoleBinding(_) => {
+                ty == "rbac.authorization.k8s.io" && version == "v1" && kind == "ClusterRoleBinding"
+            }
+
+            KubeObjectType::RbacV1RoleBinding(_) => {
+                ty == "rbac.authorization.k8s.io" && version == "v1" && kind == "RoleBinding"
+            }
+
+            KubeObjectType::StorageV1StorageClass(_) => {
+                ty == "storage.k8s.io" && version == "v1" && kind == "StorageClass"
+            }
         }
     }
 
     pub fn metadata(&self) -> Option<&ObjectMeta> {
         match *self {
+            KubeObjectType::CoreV1Namespace(ref o) => o.metadata.as_ref(),
+
             KubeObjectType::CoreV1Node(ref o) => o.metadata.as_ref(),
 
             KubeObjectType::CoreV1Pod(ref o) => o.metadata.as_ref(),
 
             KubeObjectType::CoreV1Service(ref o) => o.metadata.as_ref(),
 
+            KubeObjectType::CoreV1Secret(ref o) => o.metadata.as_ref(),
+
+            KubeObjectType::CoreV1ConfigMap(ref o) => o.metadata.as_ref(),
+
+            KubeObjectType::CoreV1LimitRange(ref o) => o.metadata.as_ref(),
+
+            KubeObjectType::CoreV1ResourceQuota(ref o) => o.metadata.as_ref(),
+
             KubeObjectType::AppsV1DaemonSet(ref o) => o.metadata.as_ref(),
 
             KubeObjectType::AppsV1Deployment(ref o) => o.metadata.as_ref(),
@@ -337,15 +508,141 @@ impl KubeObjectType {
 
             KubeObjectType::NetworkingV1beta1Ingress(ref o) => o.metadata.as_ref(),
 
+            KubeObjectType::NetworkingV1NetworkPolicy(ref o) => o.metadata.as_ref(),
+
             KubeObjectType::ExtensionsV1beta1Ingress(ref o) => o.metadata.as_ref(),
 
             KubeObjectType::RbacV1ClusterRole(ref o) => o.metadata.as_ref(),
 
             KubeObjectType::RbacV1Role(ref o) => o.metadata.as_ref(),
+
+            KubeObjectType::RbacV1ClusterRoleBinding(ref o) => o.metadata.as_ref(),
+
+            KubeObjectType::RbacV1RoleBinding(ref o) => o.metadata.as_ref(),
+
+            KubeObjectType::StorageV1StorageClass(ref o) => o.metadata.as_ref(),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match *self {
+            KubeObjectType::CoreV1Namespace(_) => "Namespace",
+
+            KubeObjectType::CoreV1Node(_) => "Node",
+
+            KubeObjectType::CoreV1Pod(_) => "Pod",
+
+            KubeObjectType::CoreV1Service(_) => "Service",
+
+            KubeObjectType::CoreV1Secret(_) => "Secret",
+
+            KubeObjectType::CoreV1ConfigMap(_) => "ConfigMap",
+
+            KubeObjectType::CoreV1LimitRange(_) => "LimitRange",
+
+            KubeObjectType::CoreV1ResourceQuota(_) => "ResourceQuota",
+
+            KubeObjectType::AppsV1DaemonSet(_) => "DaemonSet",
+
+            KubeObjectType::AppsV1Deployment(_) => "Deployment",
+
+            KubeObjectType::AppsV1ReplicaSet(_) => "ReplicaSet",
+
+            KubeObjectType::AppsV1StatefulSet(_) => "StatefulSet",
+
+            KubeObjectType::PolicyV1beta1PodDisruptionBudget(_) => "PodDisruptionBudget",
+
+            KubeObjectType::AutoscalingV1HorizontalPodAutoscaler(_) => "HorizontalPodAutoscaler",
+
+            KubeObjectType::AutoscalingV2beta1HorizontalPodAutoscaler(_) => {
+                "HorizontalPodAutoscaler"
+            }
+
+            KubeObjectType::AutoscalingV2beta2HorizontalPodAutoscaler(_) => {
+                "HorizontalPodAutoscaler"
+            }
+
+            KubeObjectType::NetworkingV1beta1Ingress(_) => "Ingress",
+
+            KubeObjectType::NetworkingV1NetworkPolicy(_) => "NetworkPolicy",
+
+            KubeObjectType::ExtensionsV1beta1Ingress(_) => "Ingress",
+
+            KubeObjectType::RbacV1ClusterRole(_) => "ClusterRole",
+
+            KubeObjectType::RbacV1Role(_) => "Role",
+
+            KubeObjectType::RbacV1ClusterRoleBinding(_) => "ClusterRoleBinding",
+
+            KubeObjectType::RbacV1RoleBinding(_) => "RoleBinding",
+
+            KubeObjectType::StorageV1StorageClass(_) => "StorageClass",
+        }
+    }
+
+    pub fn to_json(&self) -> Result<serde_json::Value, serde_json::Error> {
+        match *self {
+            KubeObjectType::CoreV1Namespace(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::CoreV1Node(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::CoreV1Pod(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::CoreV1Service(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::CoreV1Secret(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::CoreV1ConfigMap(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::CoreV1LimitRange(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::CoreV1ResourceQuota(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::AppsV1DaemonSet(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::AppsV1Deployment(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::AppsV1ReplicaSet(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::AppsV1StatefulSet(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::PolicyV1beta1PodDisruptionBudget(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::AutoscalingV1HorizontalPodAutoscaler(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::AutoscalingV2beta1HorizontalPodAutoscaler(ref o) => {
+                serde_json::to_value(o)
+            }
+
+            KubeObjectType::AutoscalingV2beta2HorizontalPodAutoscaler(ref o) => {
+                serde_json::to_value(o)
+            }
+
+            KubeObjectType::NetworkingV1beta1Ingress(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::NetworkingV1NetworkPolicy(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::ExtensionsV1beta1Ingress(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::RbacV1ClusterRole(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::RbacV1Role(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::RbacV1ClusterRoleBinding(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::RbacV1RoleBinding(ref o) => serde_json::to_value(o),
+
+            KubeObjectType::StorageV1StorageClass(ref o) => serde_json::to_value(o),
         }
     }
 }
 
+impl From<k8s_openapi::api::core::v1::Namespace> for KubeObjectType {
+    fn from(o: k8s_openapi::api::core::v1::Namespace) -> Self {
+        Self::CoreV1Namespace(Box::new(o))
+    }
+}
+
 impl From<k8s_openapi::api::core::v1::Node> for KubeObjectType {
     fn from(o: k8s_openapi::api::core::v1::Node) -> Self {
         Self::CoreV1Node(Box::new(o))
@@ -364,6 +661,30 @@ impl From<k8s_openapi::api::core::v1::Service> for KubeObjectType {
     }
 }
 
+impl From<k8s_openapi::api::core::v1::Secret> for KubeObjectType {
+    fn from(o: k8s_openapi::api::core::v1::Secret) -> Self {
+        Self::CoreV1Secret(Box::new(o))
+    }
+}
+
+impl From<k8s_openapi::api::core::v1::ConfigMap> for KubeObjectType {
+    fn from(o: k8s_openapi::api::core::v1::ConfigMap) -> Self {
+        Self::CoreV1ConfigMap(Box::new(o))
+    }
+}
+
+impl From<k8s_openapi::api::core::v1::LimitRange> for KubeObjectType {
+    fn from(o: k8s_openapi::api::core::v1::LimitRange) -> Self {
+        Self::CoreV1LimitRange(Box::new(o))
+    }
+}
+
+impl From<k8s_openapi::api::core::v1::ResourceQuota> for KubeObjectType {
+    fn from(o: k8s_openapi::api::core::v1::ResourceQuota) -> Self {
+        Self::CoreV1ResourceQuota(Box::new(o))
+    }
+}
+
 impl From<k8s_openapi::api::apps::v1::DaemonSet> for KubeObjectType {
     fn from(o: k8s_openapi::api::apps::v1::DaemonSet) -> Self {
         Self::AppsV1DaemonSet(Box::new(o))
@@ -418,6 +739,12 @@ impl From<k8s_openapi::api::networking::v1beta1::Ingress> for KubeObjectType {
     }
 }
 
+impl From<k8s_openapi::api::networking::v1::NetworkPolicy> for KubeObjectType {
+    fn from(o: k8s_openapi::api::networking::v1::NetworkPolicy) -> Self {
+        Self::NetworkingV1NetworkPolicy(Box::new(o))
+    }
+}
+
 impl From<k8s_openapi::api::extensions::v1beta1::Ingress> for KubeObjectType {
     fn from(o: k8s_openapi::api::extensions::v1beta1::Ingress) -> Self {
         Self::ExtensionsV1beta1Ingress(Box::new(o))
@@ -435,3 +762,21 @@ impl From<k8s_openapi::api::rbac::v1::Role> for KubeObjectType {
         Self::RbacV1Role(Box::new(o))
     }
 }
+
+impl From<k8s_openapi::api::rbac::v1::ClusterRoleBinding> for KubeObjectType {
+    fn from(o: k8s_openapi::api::rbac::v1::ClusterRoleBinding) -> Self {
+        Self::RbacV1ClusterRoleBinding(Box::new(o))
+    }
+}
+
+impl From<k8s_openapi::api::rbac::v1::RoleBinding> for KubeObjectType {
+    fn from(o: k8s_openapi::api::rbac::v1::RoleBinding) -> Self {
+        Self::RbacV1RoleBinding(Box::new(o))
+    }
+}
+
+impl From<k8s_openapi::api::storage::v1::StorageClass> for KubeObjectType {
+    fn from(o: k8s_openapi::api::storage::v1::StorageClass) -> Self {
+        Self::StorageV1StorageClass(Box::new(o))
+    }
+}
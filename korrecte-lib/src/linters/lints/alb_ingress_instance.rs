@@ -1,14 +1,14 @@
 use crate::linters::{KubeObjectType, Lint};
 
 use crate::f;
+use crate::kube::ingress::IngressExt;
 use crate::linters::evaluator::Context;
 use crate::reporting::Finding;
 use k8s_openapi::api::core::v1::Service;
 use k8s_openapi::api::extensions::v1beta1::Ingress as LegacyIngress;
 use k8s_openapi::api::networking::v1beta1::Ingress;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
-use std::collections::hash_map::RandomState;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::HashSet;
 
 pub(crate) struct AlbIngressInstance;
 
@@ -121,73 +121,6 @@ impl IngressType {
     }
 }
 
-trait IngressExt {
-    fn is_alb(&self) -> bool;
-    fn get_service_names(&self) -> HashSet<String>;
-    fn metadata(&self) -> Option<&ObjectMeta>;
-
-    fn has_alb_annotation(&self, annotations: Option<&BTreeMap<String, String>>) -> bool {
-        annotations
-            .and_then(|a| a.get("kubernetes.io/ingress.class"))
-            .map(|class| class == "alb")
-            .unwrap_or(false)
-    }
-}
-
-impl IngressExt for Ingress {
-    fn is_alb(&self) -> bool {
-        self.has_alb_annotation(f!(self.metadata, annotations))
-    }
-
-    fn get_service_names(&self) -> HashSet<String, RandomState> {
-        let empty = Vec::new();
-
-        f!(self.spec, rules)
-            .unwrap_or(&empty)
-            .iter()
-            .flat_map(|rule| {
-                rule.http.as_ref().map(|http| {
-                    http.paths
-                        .iter()
-                        .map(|path| path.backend.service_name.clone())
-                        .collect()
-                })
-            })
-            .collect()
-    }
-
-    fn metadata(&self) -> Option<&ObjectMeta> {
-        self.metadata.as_ref()
-    }
-}
-
-impl IngressExt for LegacyIngress {
-    fn is_alb(&self) -> bool {
-        self.has_alb_annotation(f!(self.metadata, annotations))
-    }
-
-    fn get_service_names(&self) -> HashSet<String, RandomState> {
-        let empty = Vec::new();
-
-        f!(self.spec, rules)
-            .unwrap_or(&empty)
-            .iter()
-            .flat_map(|rule| {
-                rule.http.as_ref().map(|http| {
-                    http.paths
-                        .iter()
-                        .map(|path| path.backend.service_name.clone())
-                        .collect()
-                })
-            })
-            .collect()
-    }
-
-    fn metadata(&self) -> Option<&ObjectMeta> {
-        self.metadata.as_ref()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use crate::tests::{analyze_file, filter_findings_by};
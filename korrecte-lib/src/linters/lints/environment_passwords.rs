@@ -6,7 +6,7 @@ use crate::visitor::{pod_spec_visit, PodSpecVisitor};
 use k8s_openapi::api::core::v1::PodSpec;
 use k8s_openapi::api::core::v1::{Container, EnvVar};
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 pub(crate) struct EnvironmentPasswords {
     config: Config,
@@ -73,7 +73,7 @@ impl EnvironmentPasswords {
     }
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub(crate) struct Config {
     #[serde(default = "default_environment_vars")]
     suspicious_keys: Vec<String>,
@@ -0,0 +1,143 @@
+use crate::linters::Lint;
+
+use crate::f;
+use crate::kube::hpa::{all_hpas, find_scale_target, HpaExt};
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use k8s_openapi::api::autoscaling::v1::HorizontalPodAutoscaler;
+use k8s_openapi::api::autoscaling::v2beta1::HorizontalPodAutoscaler as HpaBeta1;
+use k8s_openapi::api::autoscaling::v2beta2::HorizontalPodAutoscaler as HpaBeta2;
+
+pub(crate) struct HpaMisconfiguration;
+
+const LINT_NAME: &str = "hpa_misconfiguration";
+/// Kinds which are loaded by korrecte. HPAs targeting other kinds (like custom resources) can not
+/// be validated.
+const KNOWN_TARGET_KINDS: [&str; 3] = ["Deployment", "ReplicaSet", "StatefulSet"];
+
+impl Lint for HpaMisconfiguration {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn autoscaling_v1_horizontal_pod_autoscaler(
+        &self,
+        hpa: &HorizontalPodAutoscaler,
+        context: &Context,
+    ) {
+        self.check_hpa(hpa, context);
+    }
+
+    fn autoscaling_v2beta1_horizontal_pod_autoscaler(&self, hpa: &HpaBeta1, context: &Context) {
+        self.check_hpa(hpa, context);
+    }
+
+    fn autoscaling_v2beta2_horizontal_pod_autoscaler(&self, hpa: &HpaBeta2, context: &Context) {
+        self.check_hpa(hpa, context);
+    }
+}
+
+impl HpaMisconfiguration {
+    fn check_hpa(&self, hpa: &dyn HpaExt, context: &Context) {
+        self.check_conflicting_hpas(hpa, context);
+        self.check_missing_target(hpa, context);
+        self.check_replicas(hpa, context);
+    }
+
+    fn check_conflicting_hpas(&self, hpa: &dyn HpaExt, context: &Context) {
+        let target = match hpa.scale_target() {
+            Some(target) => target,
+            None => return,
+        };
+        let name = f!(hpa.metadata(), name);
+        let namespace = f!(hpa.metadata(), namespace);
+
+        // The same HPA may be exposed on different api versions, so HPAs with the same name are
+        // considered the same object
+        let conflicting = all_hpas(context.repository)
+            .into_iter()
+            .filter(|other| f!(other.metadata(), name) != name)
+            .filter(|other| other.targets(target.kind, target.name, namespace));
+
+        for other in conflicting {
+            let finding = self
+                .finding(hpa, "conflicting_hpa")
+                .add_metadata("target", format!("{}/{}", target.kind, target.name))
+                .add_metadata(
+                    "conflicting_hpa",
+                    f!(other.metadata(), name).cloned().unwrap_or_default(),
+                );
+            context.reporter.report(finding);
+        }
+    }
+
+    fn check_missing_target(&self, hpa: &dyn HpaExt, context: &Context) {
+        let target = match hpa.scale_target() {
+            Some(target) if KNOWN_TARGET_KINDS.contains(&target.kind) => target,
+            _ => return,
+        };
+
+        if find_scale_target(context.repository, hpa).is_none() {
+            let finding = self
+                .finding(hpa, "missing_target")
+                .add_metadata("target", format!("{}/{}", target.kind, target.name));
+            context.reporter.report(finding);
+        }
+    }
+
+    fn check_replicas(&self, hpa: &dyn HpaExt, context: &Context) {
+        let min_replicas = hpa.min_replicas();
+
+        if let Some(max_replicas) = hpa.max_replicas() {
+            if min_replicas > max_replicas {
+                let finding = self
+                    .finding(hpa, "min_replicas_greater_than_max")
+                    .add_metadata("min_replicas", min_replicas)
+                    .add_metadata("max_replicas", max_replicas);
+                context.reporter.report(finding);
+            }
+        }
+    }
+
+    fn finding(&self, hpa: &dyn HpaExt, issue: &str) -> Finding {
+        Finding::new(LINT_NAME, hpa.metadata().cloned()).add_metadata("issue", issue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_misconfigured_hpas() {
+        let findings = analyze_file(Path::new("../tests/hpa_v2beta2.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(4, findings.len());
+
+        assert_eq!("hpa-cpu-and-memory", findings[0].name());
+        assert_eq!("conflicting_hpa", findings[0].lint_metadata()["issue"]);
+        assert_eq!(
+            "worker-legacy",
+            findings[0].lint_metadata()["conflicting_hpa"]
+        );
+        assert_eq!("worker-legacy", findings[1].name());
+        assert_eq!("Deployment/worker", findings[1].lint_metadata()["target"]);
+
+        assert_eq!("hpa-missing-target", findings[2].name());
+        assert_eq!("missing_target", findings[2].lint_metadata()["issue"]);
+        assert_eq!(
+            "Deployment/does-not-exist",
+            findings[2].lint_metadata()["target"]
+        );
+
+        assert_eq!("hpa-invalid-replicas", findings[3].name());
+        assert_eq!(
+            "min_replicas_greater_than_max",
+            findings[3].lint_metadata()["issue"]
+        );
+        assert_eq!("5", findings[3].lint_metadata()["min_replicas"]);
+        assert_eq!("3", findings[3].lint_metadata()["max_replicas"]);
+    }
+}
@@ -1,12 +1,17 @@
+use crate::f;
+use crate::kube::hpa::{find_scale_target, HpaExt, ResourceMetric};
+use crate::kube::limit_range::effective_resources;
+use crate::kube::repository::ObjectRepository;
 use crate::linters::evaluator::Context;
-use crate::linters::{KubeObjectType, Lint};
+use crate::linters::Lint;
 use crate::reporting::Finding;
 use crate::visitor::{pod_spec_visit, PodSpecVisitor};
-use crate::{f, m};
 use k8s_openapi::api::autoscaling::v1::HorizontalPodAutoscaler;
-use k8s_openapi::api::autoscaling::v2beta1::HorizontalPodAutoscaler as HpaBeta2;
+use k8s_openapi::api::autoscaling::v2beta1::HorizontalPodAutoscaler as HpaBeta1;
+use k8s_openapi::api::autoscaling::v2beta2::HorizontalPodAutoscaler as HpaBeta2;
 use k8s_openapi::api::core::v1::PodSpec;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use std::collections::BTreeSet;
 
 #[derive(Default)]
 pub(crate) struct HpaNoRequest;
@@ -23,123 +28,72 @@ impl Lint for HpaNoRequest {
         hpa: &HorizontalPodAutoscaler,
         context: &Context,
     ) {
-        let cross_reference = m!(hpa.spec, scale_target_ref);
-        if cross_reference.is_none() {
-            return;
-        }
-
-        if f!(hpa.spec, target_cpu_utilization_percentage).is_none() {
-            return;
-        }
-
-        let reference = cross_reference.unwrap();
-        let matching_controller = TargetExtractor::extract(
-            &context,
-            reference.api_version.as_ref(),
-            &reference.kind,
-            &reference.name,
-        );
-
-        if matching_controller.is_none() {
-            return;
-        }
-
-        let mut v = MissingCpuRequestVisitor {
-            any_container_without_cpu: false,
-            any_container_without_mem: false,
-        };
-        pod_spec_visit(matching_controller.unwrap(), &mut v);
-
-        if v.any_container_without_cpu {
-            context
-                .reporter
-                .report(Finding::new(LINT_NAME, hpa.metadata.clone()));
-        }
+        self.check_hpa(hpa, context);
     }
 
-    fn autoscaling_v2beta1_horizontal_pod_autoscaler(&self, hpa: &HpaBeta2, context: &Context) {
-        let cross_reference = m!(hpa.spec, scale_target_ref);
-        if cross_reference.is_none() {
-            return;
-        }
+    fn autoscaling_v2beta1_horizontal_pod_autoscaler(&self, hpa: &HpaBeta1, context: &Context) {
+        self.check_hpa(hpa, context);
+    }
 
-        let reference = cross_reference.unwrap();
-        let matching_controller = TargetExtractor::extract(
-            &context,
-            reference.api_version.as_ref(),
-            &reference.kind,
-            &reference.name,
-        );
+    fn autoscaling_v2beta2_horizontal_pod_autoscaler(&self, hpa: &HpaBeta2, context: &Context) {
+        self.check_hpa(hpa, context);
+    }
+}
 
-        if matching_controller.is_none() {
+impl HpaNoRequest {
+    fn check_hpa(&self, hpa: &dyn HpaExt, context: &Context) {
+        let resources = hpa.resource_metrics();
+        if resources.is_empty() {
             return;
         }
 
-        let mut v = MissingCpuRequestVisitor {
-            any_container_without_cpu: false,
-            any_container_without_mem: false,
-        };
-        pod_spec_visit(matching_controller.unwrap(), &mut v);
-
-        f!(hpa.spec, metrics).map(|metrics| {
-            for m in metrics {
-                if m.type_ != "Resource" {
-                    break;
-                }
-
-                let metric_name = m!(m.resource, name).map(|n| n.as_str());
-                if metric_name == Some("cpu") && v.any_container_without_cpu
-                    || metric_name == Some("memory") && v.any_container_without_mem
-                {
-                    context
-                        .reporter
-                        .report(Finding::new(LINT_NAME, hpa.metadata.clone()));
-                }
-            }
-        });
-    }
-}
-
-struct TargetExtractor;
-
-impl TargetExtractor {
-    fn extract<'a>(
-        context: &'a Context,
-        api_version: Option<&'a String>,
-        kind: &'a str,
-        name: &'a str,
-    ) -> Option<&'a KubeObjectType> {
-        context.repository.iter().find(|k| {
-            let current_name = f!(k.metadata(), name).map(|meta| meta.as_str());
-
-            match api_version {
-                Some(api_version) => {
-                    k.matches_type(api_version, kind) && current_name == Some(name)
-                }
-                None => false,
+        if let Some(controller) = find_scale_target(context.repository, hpa) {
+            let mut v = MissingRequestVisitor {
+                repository: context.repository,
+                resources: &resources,
+                resources_without_request: BTreeSet::new(),
+            };
+            pod_spec_visit(controller, &mut v);
+
+            for resource in v.resources_without_request {
+                let finding = Finding::new(LINT_NAME, hpa.metadata().cloned())
+                    .add_metadata("resource", resource);
+                context.reporter.report(finding);
             }
-        })
+        }
     }
 }
 
-struct MissingCpuRequestVisitor {
-    any_container_without_cpu: bool,
-    any_container_without_mem: bool,
+struct MissingRequestVisitor<'a> {
+    repository: &'a dyn ObjectRepository,
+    resources: &'a [ResourceMetric],
+    resources_without_request: BTreeSet<String>,
 }
 
-impl PodSpecVisitor for MissingCpuRequestVisitor {
-    fn visit_pod_spec(&mut self, pod_spec: &PodSpec, _: &ObjectMeta, _: Option<&ObjectMeta>) {
-        self.any_container_without_cpu = pod_spec.containers.iter().any(|c| {
-            let requests = f!(c.resources, requests);
-            let has_cpu_request = requests.map(|r| r.contains_key("cpu")).unwrap_or(false);
-            !has_cpu_request
-        });
-
-        self.any_container_without_mem = pod_spec.containers.iter().any(|c| {
-            let requests = f!(c.resources, requests);
-            let has_mem_request = requests.map(|r| r.contains_key("memory")).unwrap_or(false);
-            !has_mem_request
-        });
+impl PodSpecVisitor for MissingRequestVisitor<'_> {
+    fn visit_pod_spec(&mut self, pod_spec: &PodSpec, _: &ObjectMeta, meta: Option<&ObjectMeta>) {
+        // Requests may be assigned by the LimitRange defaults of the namespace
+        let requests: Vec<_> = pod_spec
+            .containers
+            .iter()
+            .map(|c| {
+                let resources = effective_resources(self.repository, f!(meta, namespace), c);
+                (&c.name, resources.requests)
+            })
+            .collect();
+
+        for metric in self.resources.iter() {
+            // Container resource metrics only depend on the requests of their container
+            let missing_request = requests
+                .iter()
+                .filter(|(name, _)| metric.container.as_ref().is_none_or(|c| c == *name))
+                .any(|(_, r)| !r.contains_key(&metric.resource));
+
+            if missing_request {
+                self.resources_without_request
+                    .insert(metric.resource.clone());
+            }
+        }
     }
 }
 
@@ -158,4 +112,36 @@ mod tests {
         assert_eq!("hpa-no-cpu-request-v2-cpu", findings[1].name());
         assert_eq!("hpa-no-cpu-request-v2-mem", findings[2].name());
     }
+
+    #[test]
+    fn it_detects_missconfigured_v2beta2_hpa() {
+        let findings = analyze_file(Path::new("../tests/hpa_v2beta2.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(3, findings.len());
+        assert_eq!("hpa-external-then-memory", findings[0].name());
+        assert_eq!("memory", findings[0].lint_metadata()["resource"]);
+        assert_eq!("hpa-cpu-and-memory", findings[1].name());
+        assert_eq!("memory", findings[1].lint_metadata()["resource"]);
+        assert_eq!("hpa-container-cpu", findings[2].name());
+        assert_eq!("cpu", findings[2].lint_metadata()["resource"]);
+    }
+
+    #[test]
+    fn it_only_checks_the_container_of_container_resource_metrics() {
+        let findings = analyze_file(Path::new("../tests/hpa_container_resource.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(1, findings.len());
+        assert_eq!("hpa-sidecar-cpu", findings[0].name());
+        assert_eq!("cpu", findings[0].lint_metadata()["resource"]);
+    }
+
+    #[test]
+    fn it_takes_limit_range_defaults_into_account() {
+        let findings = analyze_file(Path::new("../tests/limit_range.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(0, findings.len());
+    }
 }
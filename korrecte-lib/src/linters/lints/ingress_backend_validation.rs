@@ -0,0 +1,246 @@
+use crate::linters::{KubeObjectType, Lint};
+
+use crate::f;
+use crate::kube::ingress::{all_ingresses, IngressBackendRef, IngressExt};
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use k8s_openapi::api::core::v1::Service;
+use k8s_openapi::api::extensions::v1beta1::Ingress as LegacyIngress;
+use k8s_openapi::api::networking::v1beta1::Ingress;
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+
+pub(crate) struct IngressBackendValidation;
+
+const LINT_NAME: &str = "ingress_backend_validation";
+
+impl Lint for IngressBackendValidation {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn networking_v1beta1_ingress(&self, ingress: &Ingress, context: &Context) {
+        self.lint_ingress(ingress, context);
+    }
+
+    fn extensions_v1beta1_ingress(&self, ingress: &LegacyIngress, context: &Context) {
+        self.lint_ingress(ingress, context);
+    }
+}
+
+impl IngressBackendValidation {
+    fn lint_ingress(&self, ingress: &dyn IngressExt, context: &Context) {
+        let mut backends = ingress.rule_backends();
+        backends.extend(ingress.default_backend());
+
+        for backend in backends.iter() {
+            self.check_backend(ingress, backend, context);
+        }
+
+        self.check_tls_secrets(ingress, context);
+        self.check_duplicated_rules(ingress, &backends, context);
+    }
+
+    fn check_backend(
+        &self,
+        ingress: &dyn IngressExt,
+        backend: &IngressBackendRef,
+        context: &Context,
+    ) {
+        let namespace = f!(ingress.metadata(), namespace);
+
+        match Self::find_service(context, &backend.service_name, namespace) {
+            None => {
+                let finding = Finding::new(self.name(), ingress.metadata().cloned())
+                    .add_metadata("missing_service", backend.service_name.clone());
+                context.reporter.report(finding);
+            }
+            Some(service) => {
+                if !Self::service_exposes_port(service, &backend.service_port) {
+                    let finding = Finding::new(self.name(), ingress.metadata().cloned())
+                        .add_metadata("service", backend.service_name.clone())
+                        .add_metadata("missing_port", port_to_string(&backend.service_port));
+                    context.reporter.report(finding);
+                }
+            }
+        }
+    }
+
+    fn check_tls_secrets(&self, ingress: &dyn IngressExt, context: &Context) {
+        let namespace = f!(ingress.metadata(), namespace);
+
+        for secret_name in ingress.tls_secret_names() {
+            let secret_exists = context.repository.iter().any(|object| match object {
+                KubeObjectType::CoreV1Secret(secret) => {
+                    f!(secret.metadata, name) == Some(&secret_name)
+                        && f!(secret.metadata, namespace) == namespace
+                }
+                _ => false,
+            });
+
+            if !secret_exists {
+                let finding = Finding::new(self.name(), ingress.metadata().cloned())
+                    .add_metadata("missing_tls_secret", secret_name);
+                context.reporter.report(finding);
+            }
+        }
+    }
+
+    fn check_duplicated_rules(
+        &self,
+        ingress: &dyn IngressExt,
+        backends: &[IngressBackendRef],
+        context: &Context,
+    ) {
+        let name = f!(ingress.metadata(), name);
+        let namespace = f!(ingress.metadata(), namespace);
+
+        // Both ingress api versions may expose the same object, so objects with the same name and
+        // namespace are considered the same ingress
+        let other_ingresses = all_ingresses(context.repository)
+            .into_iter()
+            .filter(|other| {
+                f!(other.metadata(), name) != name || f!(other.metadata(), namespace) != namespace
+            });
+
+        for other in other_ingresses {
+            let other_backends = other.rule_backends();
+
+            for backend in backends
+                .iter()
+                .filter(|b| b.host.is_some() || b.path.is_some())
+            {
+                let is_duplicated = other_backends
+                    .iter()
+                    .any(|o| o.host == backend.host && o.path == backend.path);
+
+                if is_duplicated {
+                    let rule = format!(
+                        "{}{}",
+                        backend.host.as_deref().unwrap_or("*"),
+                        backend.path.as_deref().unwrap_or("")
+                    );
+                    let other_name = f!(other.metadata(), name).cloned().unwrap_or_default();
+
+                    let finding = Finding::new(self.name(), ingress.metadata().cloned())
+                        .add_metadata("duplicated_rule", rule)
+                        .add_metadata("conflicting_ingress", other_name);
+                    context.reporter.report(finding);
+                }
+            }
+        }
+    }
+
+    fn find_service<'a>(
+        context: &'a Context,
+        name: &str,
+        namespace: Option<&String>,
+    ) -> Option<&'a Service> {
+        context.repository.iter().find_map(|object| match object {
+            KubeObjectType::CoreV1Service(service)
+                if f!(service.metadata, name).map(|n| n.as_str()) == Some(name)
+                    && f!(service.metadata, namespace) == namespace =>
+            {
+                Some(service.as_ref())
+            }
+            _ => None,
+        })
+    }
+
+    fn service_exposes_port(service: &Service, port: &IntOrString) -> bool {
+        f!(service.spec, ports)
+            .map(|ports| {
+                ports.iter().any(|service_port| match port {
+                    IntOrString::Int(number) => service_port.port == *number,
+                    IntOrString::String(name) => service_port.name.as_ref() == Some(name),
+                })
+            })
+            .unwrap_or(false)
+    }
+}
+
+fn port_to_string(port: &IntOrString) -> String {
+    match port {
+        IntOrString::Int(number) => number.to_string(),
+        IntOrString::String(name) => name.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reporting::Finding;
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_backends_with_missing_services_or_ports() {
+        let findings = analyze_file(Path::new("../tests/ingress_backend_validation.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert!(has_metadata(
+            &findings,
+            "ingress-missing-service",
+            "missing_service",
+            "non-existing-service"
+        ));
+        assert!(has_metadata(
+            &findings,
+            "ingress-missing-port",
+            "missing_port",
+            "8080"
+        ));
+        assert!(has_metadata(
+            &findings,
+            "ingress-missing-port",
+            "missing_port",
+            "https"
+        ));
+        assert!(!findings.iter().any(|f| f.name() == "ingress-ok"
+            && (f.lint_metadata().contains_key("missing_service")
+                || f.lint_metadata().contains_key("missing_port"))));
+    }
+
+    #[test]
+    fn it_finds_missing_tls_secrets() {
+        let findings = analyze_file(Path::new("../tests/ingress_backend_validation.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert!(has_metadata(
+            &findings,
+            "ingress-missing-tls",
+            "missing_tls_secret",
+            "missing-certificate"
+        ));
+        assert!(!has_metadata(
+            &findings,
+            "ingress-missing-tls",
+            "missing_tls_secret",
+            "existing-certificate"
+        ));
+    }
+
+    #[test]
+    fn it_finds_duplicated_rules_across_ingresses() {
+        let findings = analyze_file(Path::new("../tests/ingress_backend_validation.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert!(has_metadata(
+            &findings,
+            "ingress-duplicated",
+            "conflicting_ingress",
+            "ingress-ok"
+        ));
+        assert!(has_metadata(
+            &findings,
+            "ingress-ok",
+            "duplicated_rule",
+            "domain.test/api"
+        ));
+        assert_eq!(6, findings.len());
+    }
+
+    fn has_metadata(findings: &[Finding], name: &str, key: &str, value: &str) -> bool {
+        findings.iter().any(|f| {
+            f.name() == name && f.lint_metadata().get(key).map(|v| v.as_str()) == Some(value)
+        })
+    }
+}
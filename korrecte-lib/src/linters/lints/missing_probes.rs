@@ -0,0 +1,76 @@
+use crate::linters::{KubeObjectType, Lint};
+
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use crate::visitor::{pod_spec_visit, PodSpecVisitor};
+use k8s_openapi::api::core::v1::PodSpec;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+pub(crate) struct MissingProbes;
+
+const LINT_NAME: &str = "missing_probes";
+
+impl Lint for MissingProbes {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn object(&self, object: &KubeObjectType, context: &Context) {
+        // Only workloads managed by a controller are expected to run indefinitely
+        match object {
+            KubeObjectType::AppsV1Deployment(_)
+            | KubeObjectType::AppsV1DaemonSet(_)
+            | KubeObjectType::AppsV1ReplicaSet(_)
+            | KubeObjectType::AppsV1StatefulSet(_) => {
+                let mut visitor = MissingProbesVisitor { context };
+                pod_spec_visit(object, &mut visitor);
+            }
+            _ => {}
+        }
+    }
+}
+
+struct MissingProbesVisitor<'a> {
+    context: &'a Context<'a>,
+}
+
+impl PodSpecVisitor for MissingProbesVisitor<'_> {
+    fn visit_pod_spec(&mut self, pod_spec: &PodSpec, _: &ObjectMeta, meta: Option<&ObjectMeta>) {
+        for container in pod_spec.containers.iter() {
+            let mut missing = Vec::new();
+
+            if container.readiness_probe.is_none() {
+                missing.push("readiness");
+            }
+            if container.liveness_probe.is_none() {
+                missing.push("liveness");
+            }
+
+            if !missing.is_empty() {
+                let finding = Finding::new(LINT_NAME, meta.cloned())
+                    .add_metadata("container", container.name.clone())
+                    .add_metadata("missing", missing.join(","));
+                self.context.reporter.report(finding);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_workload_containers_without_probes() {
+        let findings = analyze_file(Path::new("../tests/probes.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(2, findings.len());
+        assert_eq!("deployment-without-probes", findings[0].name());
+        assert_eq!("readiness,liveness", findings[0].lint_metadata()["missing"]);
+        assert_eq!("deployment-without-liveness", findings[1].name());
+        assert_eq!("sidecar", findings[1].lint_metadata()["container"]);
+        assert_eq!("liveness", findings[1].lint_metadata()["missing"]);
+    }
+}
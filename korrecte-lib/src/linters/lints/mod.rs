@@ -1,13 +1,31 @@
 pub(crate) mod alb_ingress_instance;
 pub(crate) mod alb_named_sg;
 pub(crate) mod environment_passwords;
+pub(crate) mod hardcoded_secrets;
+pub(crate) mod hpa_misconfiguration;
 pub(crate) mod hpa_no_request;
+pub(crate) mod ingress_backend_validation;
+pub(crate) mod missing_probes;
+pub(crate) mod network_policy_default_deny;
+pub(crate) mod network_policy_uncovered_pods;
+pub(crate) mod network_policy_unused;
 pub(crate) mod never_restart_with_liveness_probe;
 pub(crate) mod overlapping_probes;
 pub(crate) mod pdb_min_replicas;
+pub(crate) mod pdb_overlapping;
 pub(crate) mod pod_requirements;
+pub(crate) mod probe_misconfiguration;
+pub(crate) mod rbac_privilege_escalation;
+pub(crate) mod rbac_secrets_access;
+pub(crate) mod rbac_wildcard_permissions;
+pub(crate) mod replica_resilience;
 pub(crate) mod required_labels;
+pub(crate) mod resource_quota_exceeded;
 pub(crate) mod role_similar_names;
+pub(crate) mod rollout_strategy;
+pub(crate) mod selector_overlap;
+pub(crate) mod service_port_mismatch;
 pub(crate) mod service_target_port;
 pub(crate) mod service_without_matching_labels;
 pub(crate) mod statefulset_grace_period_zero;
+pub(crate) mod statefulset_misconfiguration;
@@ -0,0 +1,43 @@
+use crate::linters::Lint;
+
+use crate::f;
+use crate::kube::network_policy::{namespace_network_policies, NetworkPolicyExt};
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use k8s_openapi::api::core::v1::Namespace;
+
+pub(crate) struct NetworkPolicyDefaultDeny;
+
+const LINT_NAME: &str = "network_policy_default_deny";
+
+impl Lint for NetworkPolicyDefaultDeny {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn core_v1_namespace(&self, namespace: &Namespace, context: &Context) {
+        let has_default_deny =
+            namespace_network_policies(context.repository, f!(namespace.metadata, name))
+                .any(|policy| policy.is_default_deny());
+
+        if !has_default_deny {
+            let finding = Finding::new(self.name(), namespace.metadata.clone());
+            context.reporter.report(finding);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_namespaces_without_default_deny_policy() {
+        let findings = analyze_file(Path::new("../tests/network_policies.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(1, findings.len());
+        assert_eq!("unprotected", findings[0].name());
+    }
+}
@@ -0,0 +1,56 @@
+use crate::linters::{KubeObjectType, Lint};
+
+use crate::kube::network_policy::{namespace_network_policies, NetworkPolicyExt};
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use crate::visitor::{pod_spec_visit, PodSpecVisitor};
+use k8s_openapi::api::core::v1::PodSpec;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+pub(crate) struct NetworkPolicyUncoveredPods;
+
+const LINT_NAME: &str = "network_policy_uncovered_pods";
+
+impl Lint for NetworkPolicyUncoveredPods {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn object(&self, object: &KubeObjectType, context: &Context) {
+        let mut visitor = UncoveredPodsVisitor { context };
+        pod_spec_visit(object, &mut visitor);
+    }
+}
+
+struct UncoveredPodsVisitor<'a> {
+    context: &'a Context<'a>,
+}
+
+impl PodSpecVisitor for UncoveredPodsVisitor<'_> {
+    fn visit_pod_spec(&mut self, _: &PodSpec, pod_meta: &ObjectMeta, meta: Option<&ObjectMeta>) {
+        let namespace = meta.and_then(|m| m.namespace.as_ref());
+        let is_covered = namespace_network_policies(self.context.repository, namespace)
+            .any(|policy| policy.applies_to_ingress() && policy.selects(pod_meta.labels.as_ref()));
+
+        if !is_covered {
+            let finding = Finding::new(LINT_NAME, meta.cloned());
+            self.context.reporter.report(finding);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_pods_not_selected_by_any_ingress_policy() {
+        let findings = analyze_file(Path::new("../tests/network_policies.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(2, findings.len());
+        assert_eq!("unprotected-api", findings[0].name());
+        assert_eq!("egress-only-api", findings[1].name());
+    }
+}
@@ -0,0 +1,67 @@
+use crate::linters::Lint;
+
+use crate::kube::network_policy::NetworkPolicyExt;
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use crate::visitor::{visit_all_pod_specs, PodSpecVisitor};
+use k8s_openapi::api::core::v1::PodSpec;
+use k8s_openapi::api::networking::v1::NetworkPolicy;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+pub(crate) struct NetworkPolicyUnused;
+
+const LINT_NAME: &str = "network_policy_unused";
+
+impl Lint for NetworkPolicyUnused {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn networking_v1_network_policy(&self, policy: &NetworkPolicy, context: &Context) {
+        let mut visitor = SelectedPodsVisitor {
+            policy,
+            any_pod_selected: false,
+        };
+        visit_all_pod_specs(context, &mut visitor);
+
+        if !visitor.any_pod_selected {
+            let finding = Finding::new(self.name(), policy.metadata.clone());
+            context.reporter.report(finding);
+        }
+    }
+}
+
+struct SelectedPodsVisitor<'a> {
+    policy: &'a NetworkPolicy,
+    any_pod_selected: bool,
+}
+
+impl PodSpecVisitor for SelectedPodsVisitor<'_> {
+    fn visit_pod_spec(&mut self, _: &PodSpec, pod_meta: &ObjectMeta, meta: Option<&ObjectMeta>) {
+        let policy_namespace = self
+            .policy
+            .metadata
+            .as_ref()
+            .and_then(|m| m.namespace.as_ref());
+        let pod_namespace = meta.and_then(|m| m.namespace.as_ref());
+
+        if policy_namespace == pod_namespace && self.policy.selects(pod_meta.labels.as_ref()) {
+            self.any_pod_selected = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_policies_which_do_not_select_any_pod() {
+        let findings = analyze_file(Path::new("../tests/network_policies.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(1, findings.len());
+        assert_eq!("stale-policy", findings[0].name());
+    }
+}
@@ -1,8 +1,8 @@
 use crate::linters::{KubeObjectType, Lint};
 
 use crate::linters::evaluator::Context;
-use crate::reporting::Finding;
-use crate::visitor::{pod_spec_visit, PodSpecVisitor};
+use crate::reporting::{fix, Finding, Patch};
+use crate::visitor::{pod_spec_visit, pod_template_pointer, PodSpecVisitor};
 use k8s_openapi::api::core::v1::PodSpec;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 
@@ -17,13 +17,17 @@ impl Lint for NeverRestartWithLivenessProbe {
     }
 
     fn object(&self, object: &KubeObjectType, context: &Context) {
-        let mut visitor = NeverRestartWithLivenessProbeVisitor { context };
+        let mut visitor = NeverRestartWithLivenessProbeVisitor {
+            context,
+            template_pointer: pod_template_pointer(object),
+        };
         pod_spec_visit(&object, &mut visitor);
     }
 }
 
 struct NeverRestartWithLivenessProbeVisitor<'a> {
     context: &'a Context<'a>,
+    template_pointer: Option<&'static str>,
 }
 
 impl<'a> PodSpecVisitor for NeverRestartWithLivenessProbeVisitor<'a> {
@@ -36,16 +40,34 @@ impl<'a> PodSpecVisitor for NeverRestartWithLivenessProbeVisitor<'a> {
             return;
         }
 
-        let has_any_liveness_probe = pod_spec
+        let containers_with_probe: Vec<usize> = pod_spec
             .containers
             .iter()
-            .any(|c| c.liveness_probe.is_some());
+            .enumerate()
+            .filter(|(_, c)| c.liveness_probe.is_some())
+            .map(|(index, _)| index)
+            .collect();
 
-        if !has_any_liveness_probe {
+        if containers_with_probe.is_empty() {
             return;
         }
 
-        let finding = Finding::new(LINT_NAME, meta.cloned());
+        let mut finding = Finding::new(LINT_NAME, meta.cloned());
+        if let Some(template) = self.template_pointer {
+            let operations = containers_with_probe
+                .iter()
+                .map(|index| {
+                    let index = index.to_string();
+                    fix::remove(fix::pointer(
+                        template,
+                        vec!["spec", "containers", index.as_str(), "livenessProbe"],
+                    ))
+                })
+                .collect();
+            // Removing the probe silences the finding, but the container may rely on it
+            finding = finding.with_unsafe_fix(Patch(operations));
+        }
+
         self.context.reporter.report(finding);
     }
 }
@@ -62,5 +84,10 @@ mod tests {
 
         assert_eq!(1, findings.len());
         assert_eq!(findings[0].name(), "hello-node-never-restart");
+        assert_eq!(
+            r#"[{"op":"remove","path":"/spec/containers/0/livenessProbe"}]"#,
+            findings[0].fix().unwrap().to_string()
+        );
+        assert!(findings[0].is_fix_unsafe());
     }
 }
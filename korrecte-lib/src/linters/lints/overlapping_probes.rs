@@ -39,8 +39,15 @@ impl<'a> OverlappingProbesVisitor<'a> {
     fn check_container_probes(&mut self, c: &Container, object_meta: Option<&ObjectMeta>) {
         let readiness_probe = c.readiness_probe.as_ref().map(Self::calculate_time_frame);
         let liveness_probes = c.liveness_probe.as_ref().map(Self::calculate_time_frame);
+        let startup_probe = c.startup_probe.as_ref().map(Self::calculate_time_frame);
 
         if let (Some(readiness), Some(liveness)) = (readiness_probe, liveness_probes) {
+            // Readiness and liveness probes are disabled until the startup probe succeeds
+            let (readiness, liveness) = match startup_probe {
+                Some(startup) => (readiness.after(&startup), liveness.after(&startup)),
+                None => (readiness, liveness),
+            };
+
             if readiness.overlaps_with(&liveness) {
                 let readiness_end = format!("{:?}", readiness.end);
                 let liveness_start = format!("{:?}", liveness.start);
@@ -92,6 +99,22 @@ impl TimeFrame {
     pub fn overlaps_with(&self, frame: &TimeFrame) -> bool {
         self.end > frame.start
     }
+
+    /// Delays the time frame until the first moment the given startup probe may succeed. Initial
+    /// delays are counted since the container starts, so they may already have elapsed by then.
+    pub fn after(&self, startup: &TimeFrame) -> TimeFrame {
+        if self.start >= startup.start {
+            return TimeFrame {
+                start: self.start,
+                end: self.end,
+            };
+        }
+
+        TimeFrame {
+            start: startup.start,
+            end: startup.start + (self.end - self.start),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -100,6 +123,7 @@ mod tests {
     use crate::tests::{analyze_file, filter_findings_by};
     use k8s_openapi::api::core::v1::Probe;
     use std::path::Path;
+    use std::time::Duration;
 
     #[test]
     fn it_finds_never_restart_errors() {
@@ -125,4 +149,30 @@ mod tests {
         assert_eq!(5, tf.start.as_secs());
         assert_eq!(5 + ((3 + 2) * (6 + 4)), tf.end.as_secs());
     }
+
+    #[test]
+    fn it_delays_probes_until_startup_probe_succeeds() {
+        let readiness = TimeFrame {
+            start: Duration::from_secs(0),
+            end: Duration::from_secs(10),
+        };
+        let liveness = TimeFrame {
+            start: Duration::from_secs(12),
+            end: Duration::from_secs(20),
+        };
+        let startup = TimeFrame {
+            start: Duration::from_secs(5),
+            end: Duration::from_secs(60),
+        };
+
+        assert!(!readiness.overlaps_with(&liveness));
+
+        let readiness = readiness.after(&startup);
+        let liveness = liveness.after(&startup);
+
+        assert_eq!(5, readiness.start.as_secs());
+        assert_eq!(15, readiness.end.as_secs());
+        assert_eq!(12, liveness.start.as_secs());
+        assert!(readiness.overlaps_with(&liveness));
+    }
 }
@@ -1,14 +1,10 @@
-use crate::linters::{KubeObjectType, Lint};
+use crate::linters::Lint;
 
-use crate::kube::repository::ObjectRepository;
+use crate::kube::hpa::all_hpas;
+use crate::kube::pdb::{desired_healthy, pdb_selects, pdb_targets};
 use crate::linters::evaluator::Context;
 use crate::reporting::Finding;
-use crate::{f, m};
-use k8s_openapi::api::apps::v1::Deployment;
-use k8s_openapi::api::autoscaling::v1::HorizontalPodAutoscaler;
 use k8s_openapi::api::policy::v1beta1::PodDisruptionBudget;
-use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
-use std::borrow::Borrow;
 
 pub(crate) struct PdbMinReplicas;
 
@@ -20,104 +16,43 @@ impl Lint for PdbMinReplicas {
     }
 
     fn policy_v1beta1_pod_disruption_budget(&self, pdb: &PodDisruptionBudget, context: &Context) {
-        if let Some(pdb_min_available) = Self::get_min_replicas(pdb) {
-            self.matching_deployments(pdb, context, pdb_min_available);
-            self.matching_hpas(pdb, context, pdb_min_available);
-        }
-    }
-}
-
-impl PdbMinReplicas {
-    fn get_min_replicas(pdb: &PodDisruptionBudget) -> Option<i32> {
-        if let Some(IntOrString::Int(amount)) = f!(pdb.spec, max_unavailable) {
-            return Some(*amount);
-        }
-
-        None
-    }
-
-    fn find_matching_deployments<'a>(
-        &'a self,
-        object_repository: &'a dyn ObjectRepository,
-        pdb: &PodDisruptionBudget,
-    ) -> Vec<&'a Deployment> {
-        object_repository
-            .iter()
-            .filter_map(|o| match o {
-                KubeObjectType::AppsV1Deployment(d) => Some(d),
-                _ => None,
-            })
-            .filter(|deploy| Self::deploy_matches_with_pdb(pdb, deploy))
-            .map(|o| o.borrow())
-            .collect()
-    }
+        let hpas = all_hpas(context.repository);
 
-    fn find_matching_hpa<'a>(
-        &'a self,
-        object_repository: &'a dyn ObjectRepository,
-        pdb: &PodDisruptionBudget,
-    ) -> Vec<&'a HorizontalPodAutoscaler> {
-        object_repository
+        for target in pdb_targets(context.repository)
             .iter()
-            .filter_map(|o| match o {
-                KubeObjectType::AutoscalingV1HorizontalPodAutoscaler(hpa) => Some(hpa),
-                _ => None,
-            })
-            .filter(|hpa| {
-                let kind = m!(hpa.spec, scale_target_ref, kind);
-                if let Some("Deployment") = kind.map(|r| r.as_str()) {
-                    // Check if there's any target deployment which is targeted by the PDB
-                    object_repository
-                        .iter()
-                        .filter_map(|object| match object {
-                            KubeObjectType::AppsV1Deployment(d) => Some(d),
-                            _ => None,
-                        })
-                        .any(|deploy| Self::deploy_matches_with_pdb(pdb.borrow(), &deploy))
-                } else {
-                    false
-                }
-            })
-            .map(|o| o.borrow())
-            .collect()
-    }
-
-    fn matching_deployments(
-        &self,
-        pdb: &PodDisruptionBudget,
-        context: &Context,
-        pdb_min_available: i32,
-    ) {
-        let matching_deployments = self.find_matching_deployments(context.repository, pdb);
-
-        matching_deployments.iter().for_each(|d| {
-            let deploy_replicas = *f!(d.spec, replicas).unwrap_or(&0);
-
-            if pdb_min_available >= deploy_replicas {
-                let finding = Finding::new(self.name(), pdb.metadata.clone())
-                    .add_metadata("deploy_replicas", deploy_replicas)
-                    .add_metadata("pdb_min_available", pdb_min_available.to_string());
-                context.reporter.report(finding);
+            .filter(|t| pdb_selects(pdb, t.namespace(), t.pod_labels))
+        {
+            // If the target is autoscaled, it may be scaled down to the HPA minimum replicas
+            let hpa_min_replicas = hpas
+                .iter()
+                .find(|hpa| hpa.targets(target.kind, target.name(), target.namespace()))
+                .map(|hpa| hpa.min_replicas());
+            let replicas = hpa_min_replicas.unwrap_or(target.replicas);
+
+            // Targets scaled to zero do not have any pod to protect
+            if replicas <= 0 {
+                continue;
             }
-        })
-    }
 
-    fn matching_hpas(&self, pdb: &PodDisruptionBudget, context: &Context, pdb_min_available: i32) {
-        let matching_hpa = self.find_matching_hpa(context.repository, pdb);
+            let desired_healthy = match desired_healthy(pdb, replicas) {
+                Some(desired_healthy) => desired_healthy,
+                None => continue,
+            };
+            let disruptions_allowed = replicas - desired_healthy;
+
+            if disruptions_allowed < 1 {
+                let mut finding = Finding::new(self.name(), pdb.metadata.clone())
+                    .add_metadata("target", target.reference())
+                    .add_metadata("replicas", replicas)
+                    .add_metadata("desired_healthy", desired_healthy)
+                    .add_metadata("disruptions_allowed", disruptions_allowed);
+                if let Some(hpa_min_replicas) = hpa_min_replicas {
+                    finding = finding.add_metadata("hpa_min_replicas", hpa_min_replicas);
+                }
 
-        matching_hpa.iter().for_each(|d| {
-            let hpa_replicas = *f!(d.spec, min_replicas).unwrap_or(&0);
-            if pdb_min_available >= hpa_replicas {
-                let finding = Finding::new(self.name(), pdb.metadata.clone())
-                    .add_metadata("hpa_replicas", hpa_replicas)
-                    .add_metadata("pdb_min_available", pdb_min_available.to_string());
                 context.reporter.report(finding);
             }
-        })
-    }
-
-    fn deploy_matches_with_pdb(pdb: &PodDisruptionBudget, deploy: &Deployment) -> bool {
-        f!(pdb.spec, selector) == m!(deploy.spec, selector)
+        }
     }
 }
 
@@ -161,4 +96,19 @@ mod tests {
 
         assert_eq!(0, findings.len());
     }
+
+    #[test]
+    fn test_pdb_percentages_and_other_controllers() {
+        let findings = analyze_file(Path::new("../tests/pdb_semantics.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(2, findings.len());
+        assert_eq!("db-pdb", findings[0].name());
+        assert_eq!("StatefulSet/db", findings[0].lint_metadata()["target"]);
+        assert_eq!("3", findings[0].lint_metadata()["desired_healthy"]);
+        assert_eq!("web-pdb", findings[1].name());
+        assert_eq!("Deployment/web", findings[1].lint_metadata()["target"]);
+        assert_eq!("2", findings[1].lint_metadata()["hpa_min_replicas"]);
+        assert_eq!("0", findings[1].lint_metadata()["disruptions_allowed"]);
+    }
 }
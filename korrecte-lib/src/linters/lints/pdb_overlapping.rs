@@ -0,0 +1,62 @@
+use crate::linters::Lint;
+
+use crate::f;
+use crate::kube::pdb::{matching_pdbs, pdb_selects, pdb_targets};
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use k8s_openapi::api::policy::v1beta1::PodDisruptionBudget;
+
+pub(crate) struct PdbOverlapping;
+
+const LINT_NAME: &str = "pdb_overlapping";
+
+impl Lint for PdbOverlapping {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn policy_v1beta1_pod_disruption_budget(&self, pdb: &PodDisruptionBudget, context: &Context) {
+        let name = f!(pdb.metadata, name);
+
+        for target in pdb_targets(context.repository)
+            .iter()
+            .filter(|t| pdb_selects(pdb, t.namespace(), t.pod_labels))
+        {
+            let overlapping =
+                matching_pdbs(context.repository, target.namespace(), target.pod_labels)
+                    .filter(|other| f!(other.metadata, name) != name);
+
+            for other in overlapping {
+                let finding = Finding::new(self.name(), pdb.metadata.clone())
+                    .add_metadata("target", target.reference())
+                    .add_metadata(
+                        "overlapping_pdb",
+                        f!(other.metadata, name).cloned().unwrap_or_default(),
+                    );
+                context.reporter.report(finding);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_pdbs_selecting_the_same_pods() {
+        let findings = analyze_file(Path::new("../tests/pdb_semantics.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(2, findings.len());
+        assert_eq!("web-pdb", findings[0].name());
+        assert_eq!(
+            "web-pdb-duplicated",
+            findings[0].lint_metadata()["overlapping_pdb"]
+        );
+        assert_eq!("Deployment/web", findings[0].lint_metadata()["target"]);
+        assert_eq!("web-pdb-duplicated", findings[1].name());
+        assert_eq!("web-pdb", findings[1].lint_metadata()["overlapping_pdb"]);
+    }
+}
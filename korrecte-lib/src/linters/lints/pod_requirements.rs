@@ -1,17 +1,30 @@
 use crate::linters::{KubeObjectType, Lint};
 
+use crate::f;
+use crate::kube::limit_range::effective_resources;
+use crate::kube::namespace::namespace_matches;
+use crate::kube::quantity::{parse_quantity, QuantityExt};
 use crate::linters::evaluator::Context;
 use crate::reporting::Finding;
 use crate::visitor::{pod_spec_visit, PodSpecVisitor};
 use k8s_openapi::api::core::v1::Container;
 use k8s_openapi::api::core::v1::PodSpec;
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
-use std::collections::BTreeMap;
+use serde::{Deserialize, Deserializer, Serialize};
 
-#[derive(Default)]
-pub(crate) struct PodRequirements;
+pub(crate) struct PodRequirements {
+    config: Config,
+}
+
+impl PodRequirements {
+    pub fn new(config: Config) -> Self {
+        PodRequirements { config }
+    }
+}
 
 const LINT_NAME: &str = "pod_requirements";
+const CHECKED_RESOURCES: [&str; 3] = ["cpu", "memory", "ephemeral-storage"];
 
 impl Lint for PodRequirements {
     fn name(&self) -> &str {
@@ -19,13 +32,17 @@ impl Lint for PodRequirements {
     }
 
     fn object(&self, object: &KubeObjectType, context: &Context) {
-        let mut visitor = PodRequirementsVisitor { context };
+        let mut visitor = PodRequirementsVisitor {
+            context,
+            config: &self.config,
+        };
         pod_spec_visit(&object, &mut visitor);
     }
 }
 
 struct PodRequirementsVisitor<'a> {
     context: &'a Context<'a>,
+    config: &'a Config,
 }
 
 impl<'a> PodSpecVisitor for PodRequirementsVisitor<'a> {
@@ -36,44 +53,164 @@ impl<'a> PodSpecVisitor for PodRequirementsVisitor<'a> {
 
 impl<'a> PodRequirementsVisitor<'a> {
     fn check_pod_spec(&self, pod_spec: &PodSpec, metadata: Option<&ObjectMeta>) {
-        for container in pod_spec.containers.iter() {
+        let init_containers = pod_spec.init_containers.iter().flatten();
+
+        for container in pod_spec.containers.iter().chain(init_containers) {
             self.check_container(container, metadata);
         }
     }
 
     fn check_container(&self, container: &Container, metadata: Option<&ObjectMeta>) {
-        match container.resources {
-            None => {
-                self.missing_cpu_limit(metadata, container);
-                self.missing_mem_limit(metadata, container);
-                self.missing_cpu_requirement(metadata, container);
-                self.missing_mem_requirement(metadata, container);
+        let resources =
+            effective_resources(self.context.repository, f!(metadata, namespace), container);
+        let limits = &resources.limits;
+        let requests = &resources.requests;
+
+        // Check limits
+        if !limits.contains_key("cpu") {
+            self.missing_cpu_limit(metadata, container);
+        }
+        if !limits.contains_key("memory") {
+            self.missing_mem_limit(metadata, container);
+        }
+
+        // Check requirements
+        if !requests.contains_key("cpu") {
+            self.missing_cpu_requirement(metadata, container);
+        }
+        if !requests.contains_key("memory") {
+            self.missing_mem_requirement(metadata, container);
+        }
+
+        for resource in CHECKED_RESOURCES.iter() {
+            let request = self.parse(metadata, container, resource, requests.get(*resource));
+            let limit = self.parse(metadata, container, resource, limits.get(*resource));
+
+            self.check_request_and_limit(metadata, container, resource, request, limit);
+            self.check_bounds(metadata, container, resource, "request", request);
+            self.check_bounds(metadata, container, resource, "limit", limit);
+        }
+    }
+
+    fn parse(
+        &self,
+        metadata: Option<&ObjectMeta>,
+        container: &Container,
+        resource: &str,
+        quantity: Option<&Quantity>,
+    ) -> Option<f64> {
+        let quantity = quantity?;
+        let value = quantity.value();
+
+        if value.is_none() {
+            let finding = self
+                .finding(metadata, container, resource, "invalid_quantity")
+                .add_metadata("value", quantity.0.clone());
+            self.context.reporter.report(finding);
+        }
+
+        value
+    }
+
+    fn check_request_and_limit(
+        &self,
+        metadata: Option<&ObjectMeta>,
+        container: &Container,
+        resource: &str,
+        request: Option<f64>,
+        limit: Option<f64>,
+    ) {
+        let (request, limit) = match (request, limit) {
+            (Some(request), Some(limit)) => (request, limit),
+            _ => return,
+        };
+
+        if request > limit {
+            let finding = self
+                .finding(metadata, container, resource, "request_greater_than_limit")
+                .add_metadata("request", request)
+                .add_metadata("limit", limit);
+            self.context.reporter.report(finding);
+            return;
+        }
+
+        if let Some(max_ratio) = self.config.max_limit_request_ratio {
+            let ratio = limit / request;
+
+            if request > 0.0 && ratio > max_ratio {
+                let finding = self
+                    .finding(
+                        metadata,
+                        container,
+                        resource,
+                        "limit_request_ratio_exceeded",
+                    )
+                    .add_metadata("request", request)
+                    .add_metadata("limit", limit)
+                    .add_metadata("ratio", ratio)
+                    .add_metadata("max_ratio", max_ratio);
+                self.context.reporter.report(finding);
             }
-            Some(ref req) => {
-                let empty_map = BTreeMap::new();
-
-                // TODO: Move into dedicated methods
-                // Check limits
-                let limits = req.limits.as_ref().unwrap_or(&empty_map);
-                if !limits.contains_key("cpu") {
-                    self.missing_cpu_limit(metadata, container);
-                }
-                if !limits.contains_key("memory") {
-                    self.missing_mem_limit(metadata, container);
-                }
-
-                // Check requirements
-                let requests = req.requests.as_ref().unwrap_or(&empty_map);
-                if !requests.contains_key("cpu") {
-                    self.missing_cpu_requirement(metadata, container);
-                }
-                if !requests.contains_key("memory") {
-                    self.missing_mem_requirement(metadata, container);
-                }
+        }
+    }
+
+    fn check_bounds(
+        &self,
+        metadata: Option<&ObjectMeta>,
+        container: &Container,
+        resource: &str,
+        kind: &str,
+        value: Option<f64>,
+    ) {
+        let value = match value {
+            Some(value) => value,
+            None => return,
+        };
+        let namespace = f!(metadata, namespace);
+
+        for bounds in self.config.bounds_for(namespace, resource) {
+            let below_min = bounds
+                .min
+                .as_ref()
+                .and_then(|min| min.value())
+                .filter(|min| value < *min);
+            let above_max = bounds
+                .max
+                .as_ref()
+                .and_then(|max| max.value())
+                .filter(|max| value > *max);
+
+            if let Some(min) = below_min {
+                let finding = self
+                    .finding(metadata, container, resource, "below_min")
+                    .add_metadata(kind, value)
+                    .add_metadata("min", min);
+                self.context.reporter.report(finding);
+            }
+
+            if let Some(max) = above_max {
+                let finding = self
+                    .finding(metadata, container, resource, "above_max")
+                    .add_metadata(kind, value)
+                    .add_metadata("max", max);
+                self.context.reporter.report(finding);
             }
         }
     }
 
+    fn finding(
+        &self,
+        metadata: Option<&ObjectMeta>,
+        container: &Container,
+        resource: &str,
+        issue: &str,
+    ) -> Finding {
+        Finding::new(LINT_NAME, metadata.cloned())
+            .add_metadata("issue", issue)
+            .add_metadata("container", container.name.clone())
+            .add_metadata("resource", resource)
+    }
+
     fn missing_cpu_limit(&self, metadata: Option<&ObjectMeta>, container: &Container) {
         self.missing_resource(metadata, container, "missing_cpu_limit");
     }
@@ -90,18 +227,87 @@ impl<'a> PodRequirementsVisitor<'a> {
         self.missing_resource(metadata, container, "missing_mem_requirement");
     }
 
-    fn missing_resource(&self, metadata: Option<&ObjectMeta>, container: &Container, key: &str) {
+    fn missing_resource(&self, metadata: Option<&ObjectMeta>, container: &Container, issue: &str) {
         let finding = Finding::new(LINT_NAME, metadata.cloned())
-            .add_metadata(key, "")
+            .add_metadata("issue", issue)
             .add_metadata("container", container.name.clone());
 
         self.context.reporter.report(finding);
     }
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub(crate) struct Config {
+    /// Maximum allowed ratio between the limit and the request of any resource
+    #[serde(default)]
+    max_limit_request_ratio: Option<f64>,
+    #[serde(default)]
+    bounds: Vec<ResourceBounds>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub(crate) struct ResourceBounds {
+    /// Namespaces, or glob patterns, where the bounds are enforced. If empty, they are enforced
+    /// on all namespaces
+    #[serde(default)]
+    namespaces: Vec<String>,
+    #[serde(deserialize_with = "deserialize_resource")]
+    resource: String,
+    #[serde(default, deserialize_with = "deserialize_bound")]
+    min: Option<Quantity>,
+    #[serde(default, deserialize_with = "deserialize_bound")]
+    max: Option<Quantity>,
+}
+
+impl Config {
+    fn bounds_for<'a>(
+        &'a self,
+        namespace: Option<&'a String>,
+        resource: &'a str,
+    ) -> impl Iterator<Item = &'a ResourceBounds> {
+        self.bounds.iter().filter(move |bounds| {
+            bounds.resource == resource && namespace_matches(&bounds.namespaces, namespace)
+        })
+    }
+}
+
+fn deserialize_resource<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let resource = String::deserialize(deserializer)?;
+
+    if CHECKED_RESOURCES.contains(&resource.as_str()) {
+        Ok(resource)
+    } else {
+        Err(serde::de::Error::custom(format!(
+            "unknown resource `{}`, expected one of {}",
+            resource,
+            CHECKED_RESOURCES.join(", ")
+        )))
+    }
+}
+
+fn deserialize_bound<'de, D>(deserializer: D) -> Result<Option<Quantity>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let quantity = String::deserialize(deserializer)?;
+
+    match parse_quantity(&quantity) {
+        Some(_) => Ok(Some(Quantity(quantity))),
+        None => Err(serde::de::Error::custom(format!(
+            "invalid quantity `{}`",
+            quantity
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::tests::{analyze_file, filter_findings_by};
+    use super::Config;
+    use crate::reporting::Finding;
+    use crate::tests::{analyze_file, analyze_file_cfg, filter_findings_by};
     use std::path::Path;
 
     #[test]
@@ -119,4 +325,115 @@ mod tests {
 
         assert_eq!(8, findings.len());
     }
+
+    #[test]
+    pub fn it_takes_limit_range_defaults_into_account() {
+        let findings = analyze_file(Path::new("../tests/limit_range.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(4, findings.len());
+        assert!(findings.iter().all(|f| f.name() == "pod-without-defaults"));
+    }
+
+    #[test]
+    pub fn it_finds_requests_greater_than_limits() {
+        let findings = analyze_file(Path::new("../tests/pod_requirements_policy.yaml"));
+        let findings = with_issue(
+            filter_findings_by(findings, super::LINT_NAME),
+            "request_greater_than_limit",
+        );
+
+        assert_eq!(2, findings.len());
+        assert_eq!("request-greater-than-limit", findings[0].name());
+        assert_eq!("memory", findings[0].lint_metadata()["resource"]);
+        assert_eq!("1073741824", findings[0].lint_metadata()["request"]);
+        assert_eq!("536870912", findings[0].lint_metadata()["limit"]);
+        assert_eq!(
+            "init-container-request-greater-than-limit",
+            findings[1].name()
+        );
+        assert_eq!("migrations", findings[1].lint_metadata()["container"]);
+        assert_eq!("ephemeral-storage", findings[1].lint_metadata()["resource"]);
+    }
+
+    #[test]
+    pub fn it_finds_invalid_quantities() {
+        let findings = analyze_file(Path::new("../tests/pod_requirements_policy.yaml"));
+        let findings = with_issue(
+            filter_findings_by(findings, super::LINT_NAME),
+            "invalid_quantity",
+        );
+
+        assert_eq!(1, findings.len());
+        assert_eq!("invalid-quantity", findings[0].name());
+        assert_eq!("1 GB", findings[0].lint_metadata()["value"]);
+    }
+
+    #[test]
+    pub fn it_finds_ratios_and_bounds_from_config() {
+        let config: Config = toml::from_str(
+            r#"
+max_limit_request_ratio = 4.0
+
+[[bounds]]
+namespaces = ["prod*"]
+resource = "memory"
+min = "64Mi"
+max = "2Gi"
+"#,
+        )
+        .unwrap();
+        let global_config = crate::config::Config {
+            pod_requirements: config,
+            ..Default::default()
+        };
+
+        let findings = analyze_file_cfg(
+            Path::new("../tests/pod_requirements_policy.yaml"),
+            global_config,
+        );
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        let ratio = with_issue(findings.clone(), "limit_request_ratio_exceeded");
+        assert_eq!(1, ratio.len());
+        assert_eq!("high-limit-request-ratio", ratio[0].name());
+        assert_eq!("cpu", ratio[0].lint_metadata()["resource"]);
+        assert_eq!("10", ratio[0].lint_metadata()["ratio"]);
+
+        let below_min = with_issue(findings.clone(), "below_min");
+        assert_eq!(2, below_min.len());
+        assert_eq!("tiny-memory", below_min[0].name());
+        assert_eq!("1024", below_min[0].lint_metadata()["request"]);
+        assert_eq!("67108864", below_min[0].lint_metadata()["min"]);
+        assert_eq!("1024", below_min[1].lint_metadata()["limit"]);
+
+        let above_max = with_issue(findings, "above_max");
+        assert_eq!(1, above_max.len());
+        assert_eq!("huge-memory", above_max[0].name());
+        assert_eq!("4294967296", above_max[0].lint_metadata()["limit"]);
+    }
+
+    #[test]
+    pub fn it_rejects_invalid_bounds() {
+        let invalid_quantity = r#"
+[[bounds]]
+resource = "memory"
+max = "2 GB"
+"#;
+        let unknown_resource = r#"
+[[bounds]]
+resource = "cpuu"
+max = "2"
+"#;
+
+        assert!(toml::from_str::<Config>(invalid_quantity).is_err());
+        assert!(toml::from_str::<Config>(unknown_resource).is_err());
+    }
+
+    fn with_issue(findings: Vec<Finding>, issue: &str) -> Vec<Finding> {
+        findings
+            .into_iter()
+            .filter(|f| f.lint_metadata()["issue"] == issue)
+            .collect()
+    }
 }
@@ -0,0 +1,183 @@
+use crate::linters::{KubeObjectType, Lint};
+
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use crate::visitor::{pod_spec_visit, PodSpecVisitor};
+use k8s_openapi::api::core::v1::{Container, PodSpec, Probe};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+
+pub(crate) struct ProbeMisconfiguration;
+
+const LINT_NAME: &str = "probe_misconfiguration";
+const DEFAULT_TIMEOUT: i32 = 1;
+const DEFAULT_PERIOD: i32 = 10;
+
+impl Lint for ProbeMisconfiguration {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn object(&self, object: &KubeObjectType, context: &Context) {
+        let mut visitor = ProbeMisconfigurationVisitor { context };
+        pod_spec_visit(object, &mut visitor);
+    }
+}
+
+struct ProbeMisconfigurationVisitor<'a> {
+    context: &'a Context<'a>,
+}
+
+impl PodSpecVisitor for ProbeMisconfigurationVisitor<'_> {
+    fn visit_pod_spec(&mut self, pod_spec: &PodSpec, _: &ObjectMeta, meta: Option<&ObjectMeta>) {
+        for container in pod_spec.containers.iter() {
+            let probes = vec![
+                ("startup", container.startup_probe.as_ref()),
+                ("readiness", container.readiness_probe.as_ref()),
+                ("liveness", container.liveness_probe.as_ref()),
+            ];
+
+            for (kind, probe) in probes {
+                if let Some(probe) = probe {
+                    self.check_port(container, kind, probe, meta);
+                    self.check_timeout(container, kind, probe, meta);
+                }
+            }
+
+            if container.liveness_probe.is_some()
+                && container.liveness_probe == container.readiness_probe
+            {
+                let finding = self
+                    .finding(container, "liveness", meta)
+                    .add_metadata("issue", "identical_to_readiness");
+                self.context.reporter.report(finding);
+            }
+        }
+    }
+}
+
+impl ProbeMisconfigurationVisitor<'_> {
+    fn check_port(
+        &self,
+        container: &Container,
+        kind: &str,
+        probe: &Probe,
+        meta: Option<&ObjectMeta>,
+    ) {
+        let port = probe
+            .http_get
+            .as_ref()
+            .map(|http| &http.port)
+            .or_else(|| probe.tcp_socket.as_ref().map(|tcp| &tcp.port));
+        let declared_ports = container.ports.as_ref();
+
+        let exposed = match port {
+            None => true,
+            Some(IntOrString::Int(number)) => declared_ports
+                // Declaring container ports is optional, so numeric ports can only be validated
+                // if the container declares any
+                .map(|ports| ports.is_empty() || ports.iter().any(|p| p.container_port == *number))
+                .unwrap_or(true),
+            Some(IntOrString::String(name)) => declared_ports
+                .map(|ports| ports.iter().any(|p| p.name.as_ref() == Some(name)))
+                .unwrap_or(false),
+        };
+
+        if !exposed {
+            let port = match port {
+                Some(IntOrString::Int(number)) => number.to_string(),
+                Some(IntOrString::String(name)) => name.clone(),
+                None => String::new(),
+            };
+
+            let finding = self
+                .finding(container, kind, meta)
+                .add_metadata("issue", "unknown_port")
+                .add_metadata("port", port);
+            self.context.reporter.report(finding);
+        }
+    }
+
+    fn check_timeout(
+        &self,
+        container: &Container,
+        kind: &str,
+        probe: &Probe,
+        meta: Option<&ObjectMeta>,
+    ) {
+        let timeout = probe.timeout_seconds.unwrap_or(DEFAULT_TIMEOUT);
+        let period = probe.period_seconds.unwrap_or(DEFAULT_PERIOD);
+
+        if timeout >= period {
+            let finding = self
+                .finding(container, kind, meta)
+                .add_metadata("issue", "timeout_not_lower_than_period")
+                .add_metadata("timeout_seconds", timeout)
+                .add_metadata("period_seconds", period);
+            self.context.reporter.report(finding);
+        }
+    }
+
+    fn finding(&self, container: &Container, kind: &str, meta: Option<&ObjectMeta>) -> Finding {
+        Finding::new(LINT_NAME, meta.cloned())
+            .add_metadata("container", container.name.clone())
+            .add_metadata("probe", kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reporting::Finding;
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_probes_on_unknown_ports() {
+        let findings = analyze_file(Path::new("../tests/probes.yaml"));
+        let findings = with_issue(
+            filter_findings_by(findings, super::LINT_NAME),
+            "unknown_port",
+        );
+
+        assert_eq!(2, findings.len());
+        assert_eq!("probes-unknown-ports", findings[0].name());
+        assert_eq!("readiness", findings[0].lint_metadata()["probe"]);
+        assert_eq!("9090", findings[0].lint_metadata()["port"]);
+        assert_eq!("liveness", findings[1].lint_metadata()["probe"]);
+        assert_eq!("admin", findings[1].lint_metadata()["port"]);
+    }
+
+    #[test]
+    fn it_finds_timeouts_greater_or_equal_than_period() {
+        let findings = analyze_file(Path::new("../tests/probes.yaml"));
+        let findings = with_issue(
+            filter_findings_by(findings, super::LINT_NAME),
+            "timeout_not_lower_than_period",
+        );
+
+        assert_eq!(1, findings.len());
+        assert_eq!("probes-slow-timeout", findings[0].name());
+        assert_eq!("startup", findings[0].lint_metadata()["probe"]);
+        assert_eq!("5", findings[0].lint_metadata()["timeout_seconds"]);
+        assert_eq!("5", findings[0].lint_metadata()["period_seconds"]);
+    }
+
+    #[test]
+    fn it_finds_liveness_probes_identical_to_readiness() {
+        let findings = analyze_file(Path::new("../tests/probes.yaml"));
+        let findings = with_issue(
+            filter_findings_by(findings, super::LINT_NAME),
+            "identical_to_readiness",
+        );
+
+        assert_eq!(1, findings.len());
+        assert_eq!("probes-identical", findings[0].name());
+    }
+
+    fn with_issue(findings: Vec<Finding>, issue: &str) -> Vec<Finding> {
+        findings
+            .into_iter()
+            .filter(|f| f.lint_metadata().get("issue").map(|i| i.as_str()) == Some(issue))
+            .collect()
+    }
+}
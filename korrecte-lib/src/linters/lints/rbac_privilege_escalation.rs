@@ -0,0 +1,106 @@
+use crate::linters::Lint;
+
+use crate::f;
+use crate::kube::rbac::{bound_subjects, PolicyRuleExt};
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use k8s_openapi::api::rbac::v1::{ClusterRole, PolicyRule, Role};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+pub(crate) struct RbacPrivilegeEscalation;
+
+const LINT_NAME: &str = "rbac_privilege_escalation";
+
+// List of (verb, api group, resource) which allows a subject to gain more privileges than the
+// ones granted by the role
+const DANGEROUS_PERMISSIONS: &[(&str, &str, &str)] = &[
+    ("escalate", "rbac.authorization.k8s.io", "roles"),
+    ("escalate", "rbac.authorization.k8s.io", "clusterroles"),
+    ("bind", "rbac.authorization.k8s.io", "roles"),
+    ("bind", "rbac.authorization.k8s.io", "clusterroles"),
+    ("impersonate", "", "users"),
+    ("impersonate", "", "groups"),
+    ("impersonate", "", "serviceaccounts"),
+    ("create", "", "pods/exec"),
+    ("create", "", "pods"),
+    ("create", "", "replicationcontrollers"),
+    ("create", "apps", "deployments"),
+    ("create", "apps", "daemonsets"),
+    ("create", "apps", "replicasets"),
+    ("create", "apps", "statefulsets"),
+    ("create", "batch", "jobs"),
+    ("create", "batch", "cronjobs"),
+];
+
+impl Lint for RbacPrivilegeEscalation {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn rbac_v1_cluster_role(&self, cluster_role: &ClusterRole, context: &Context) {
+        if let Some(ref rules) = cluster_role.rules {
+            Self::check_rules(context, rules, "ClusterRole", &cluster_role.metadata);
+        }
+    }
+
+    fn rbac_v1_role(&self, role: &Role, context: &Context) {
+        if let Some(ref rules) = role.rules {
+            Self::check_rules(context, rules, "Role", &role.metadata);
+        }
+    }
+}
+
+impl RbacPrivilegeEscalation {
+    fn check_rules(context: &Context, rules: &[PolicyRule], kind: &str, meta: &Option<ObjectMeta>) {
+        let permissions: Vec<String> = DANGEROUS_PERMISSIONS
+            .iter()
+            .filter(|(verb, group, resource)| {
+                rules.iter().any(|rule| rule.allows(verb, group, resource))
+            })
+            .map(|(verb, _, resource)| format!("{} {}", verb, resource))
+            .collect();
+
+        if permissions.is_empty() {
+            return;
+        }
+
+        let subjects = bound_subjects(
+            context.repository,
+            kind,
+            f!(meta, name).map(|n| n.as_str()).unwrap_or_default(),
+            f!(meta, namespace),
+        );
+
+        let finding = Finding::new(LINT_NAME, meta.clone())
+            .add_metadata("permissions", permissions.join(","))
+            .add_metadata("subjects", subjects.join(","));
+        context.reporter.report(finding);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_roles_which_allow_privilege_escalation() {
+        let findings = analyze_file(Path::new("../tests/rbac_risks.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(4, findings.len());
+        assert_eq!("cluster-admin-like", findings[0].name());
+        assert_eq!("escalator", findings[1].name());
+        assert_eq!(
+            "escalate clusterroles,bind clusterroles,impersonate users",
+            findings[1].lint_metadata()["permissions"]
+        );
+        assert_eq!("namespaced-wildcard", findings[2].name());
+        assert_eq!("debugger", findings[3].name());
+        assert_eq!(
+            "create pods/exec,create deployments",
+            findings[3].lint_metadata()["permissions"]
+        );
+        assert_eq!("User:bob", findings[3].lint_metadata()["subjects"]);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::linters::Lint;
+
+use crate::f;
+use crate::kube::rbac::{cluster_role_subjects, PolicyRuleExt};
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use k8s_openapi::api::rbac::v1::ClusterRole;
+
+pub(crate) struct RbacSecretsAccess;
+
+const LINT_NAME: &str = "rbac_secrets_access";
+const READ_VERBS: &[&str] = &["get", "list", "watch"];
+
+impl Lint for RbacSecretsAccess {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn rbac_v1_cluster_role(&self, cluster_role: &ClusterRole, context: &Context) {
+        let rules = match cluster_role.rules {
+            Some(ref rules) => rules,
+            None => return,
+        };
+
+        let granted_verbs: Vec<&str> = READ_VERBS
+            .iter()
+            .filter(|verb| rules.iter().any(|r| r.allows(verb, "", "secrets")))
+            .cloned()
+            .collect();
+
+        if granted_verbs.is_empty() {
+            return;
+        }
+
+        let subjects = cluster_role_subjects(
+            context.repository,
+            f!(cluster_role.metadata, name)
+                .map(|n| n.as_str())
+                .unwrap_or_default(),
+        );
+
+        // Role bindings only grant access to the secrets of their namespace
+        if subjects.cluster.is_empty() {
+            return;
+        }
+
+        let finding = Finding::new(LINT_NAME, cluster_role.metadata.clone())
+            .add_metadata("verbs", granted_verbs.join(","))
+            .add_metadata("subjects", subjects.cluster.join(","))
+            .add_metadata("namespaced_subjects", subjects.namespaced.join(","));
+        context.reporter.report(finding);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_cluster_roles_which_can_read_secrets_cluster_wide() {
+        let findings = analyze_file(Path::new("../tests/rbac_risks.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(2, findings.len());
+        assert_eq!("cluster-admin-like", findings[0].name());
+        assert_eq!("get,list,watch", findings[0].lint_metadata()["verbs"]);
+        assert_eq!(
+            "Group:admins,User:alice",
+            findings[0].lint_metadata()["subjects"]
+        );
+        assert_eq!("", findings[0].lint_metadata()["namespaced_subjects"]);
+        assert_eq!("secret-reader", findings[1].name());
+        assert_eq!("get,list", findings[1].lint_metadata()["verbs"]);
+        assert_eq!("User:carol", findings[1].lint_metadata()["subjects"]);
+        assert_eq!(
+            "ServiceAccount:monitoring/agent (monitoring)",
+            findings[1].lint_metadata()["namespaced_subjects"]
+        );
+    }
+}
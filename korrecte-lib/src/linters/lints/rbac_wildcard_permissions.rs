@@ -0,0 +1,82 @@
+use crate::linters::Lint;
+
+use crate::f;
+use crate::kube::rbac::{bound_subjects, PolicyRuleExt};
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use k8s_openapi::api::rbac::v1::{ClusterRole, PolicyRule, Role};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+pub(crate) struct RbacWildcardPermissions;
+
+const LINT_NAME: &str = "rbac_wildcard_permissions";
+
+impl Lint for RbacWildcardPermissions {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn rbac_v1_cluster_role(&self, cluster_role: &ClusterRole, context: &Context) {
+        if let Some(ref rules) = cluster_role.rules {
+            Self::check_rules(context, rules, "ClusterRole", &cluster_role.metadata);
+        }
+    }
+
+    fn rbac_v1_role(&self, role: &Role, context: &Context) {
+        if let Some(ref rules) = role.rules {
+            Self::check_rules(context, rules, "Role", &role.metadata);
+        }
+    }
+}
+
+impl RbacWildcardPermissions {
+    fn check_rules(context: &Context, rules: &[PolicyRule], kind: &str, meta: &Option<ObjectMeta>) {
+        for rule in rules {
+            let wildcards = rule.wildcard_fields();
+            if wildcards.is_empty() {
+                continue;
+            }
+
+            let subjects = bound_subjects(
+                context.repository,
+                kind,
+                f!(meta, name).map(|n| n.as_str()).unwrap_or_default(),
+                f!(meta, namespace),
+            );
+
+            let finding = Finding::new(LINT_NAME, meta.clone())
+                .add_metadata("wildcards", wildcards.join(","))
+                .add_metadata("subjects", subjects.join(","));
+            context.reporter.report(finding);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_wildcards_on_roles() {
+        let findings = analyze_file(Path::new("../tests/rbac_risks.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(2, findings.len());
+        assert_eq!("cluster-admin-like", findings[0].name());
+        assert_eq!(
+            "verbs,resources,api_groups",
+            findings[0].lint_metadata()["wildcards"]
+        );
+        assert_eq!(
+            "Group:admins,User:alice",
+            findings[0].lint_metadata()["subjects"]
+        );
+        assert_eq!("namespaced-wildcard", findings[1].name());
+        assert_eq!("verbs", findings[1].lint_metadata()["wildcards"]);
+        assert_eq!(
+            "ServiceAccount:team/deployer",
+            findings[1].lint_metadata()["subjects"]
+        );
+    }
+}
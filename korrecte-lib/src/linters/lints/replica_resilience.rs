@@ -0,0 +1,119 @@
+use crate::linters::Lint;
+
+use crate::kube::pdb::matching_pdbs;
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use crate::{f, m};
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use k8s_openapi::api::core::v1::{PodSpec, PodTemplateSpec};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+pub(crate) struct ReplicaResilience;
+
+const LINT_NAME: &str = "replica_resilience";
+
+impl Lint for ReplicaResilience {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn apps_v1_deployment(&self, deployment: &Deployment, context: &Context) {
+        let replicas = f!(deployment.spec, replicas).cloned().unwrap_or(1);
+        let template = m!(deployment.spec, template);
+
+        self.check_replicated(deployment.metadata.as_ref(), replicas, template, context);
+    }
+
+    fn apps_v1_stateful_set(&self, stateful_set: &StatefulSet, context: &Context) {
+        let replicas = f!(stateful_set.spec, replicas).cloned().unwrap_or(1);
+        let template = m!(stateful_set.spec, template);
+
+        self.check_replicated(stateful_set.metadata.as_ref(), replicas, template, context);
+    }
+}
+
+impl ReplicaResilience {
+    fn check_replicated(
+        &self,
+        meta: Option<&ObjectMeta>,
+        replicas: i32,
+        template: Option<&PodTemplateSpec>,
+        context: &Context,
+    ) {
+        if replicas <= 1 {
+            return;
+        }
+
+        let mut missing = Vec::new();
+
+        if !template
+            .and_then(|t| t.spec.as_ref())
+            .map(Self::spreads_replicas)
+            .unwrap_or(false)
+        {
+            missing.push("spreading");
+        }
+
+        let pod_labels = template.and_then(|t| f!(t.metadata, labels));
+        let has_pdb = matching_pdbs(context.repository, f!(meta, namespace), pod_labels)
+            .next()
+            .is_some();
+        if !has_pdb {
+            missing.push("pdb");
+        }
+
+        if !missing.is_empty() {
+            let finding = Finding::new(LINT_NAME, meta.cloned())
+                .add_metadata("replicas", replicas)
+                .add_metadata("missing", missing.join(","));
+            context.reporter.report(finding);
+        }
+    }
+
+    /// Checks if the pod spec asks the scheduler to place the replicas on different failure
+    /// domains, either with pod anti-affinity or with topology spread constraints
+    fn spreads_replicas(pod_spec: &PodSpec) -> bool {
+        let anti_affinity = f!(pod_spec.affinity, pod_anti_affinity);
+        let has_anti_affinity = anti_affinity
+            .map(|a| {
+                let required = a
+                    .required_during_scheduling_ignored_during_execution
+                    .as_ref();
+                let preferred = a
+                    .preferred_during_scheduling_ignored_during_execution
+                    .as_ref();
+
+                required.map(|r| !r.is_empty()).unwrap_or(false)
+                    || preferred.map(|p| !p.is_empty()).unwrap_or(false)
+            })
+            .unwrap_or(false);
+        let has_topology_spread = pod_spec
+            .topology_spread_constraints
+            .as_ref()
+            .map(|c| !c.is_empty())
+            .unwrap_or(false);
+
+        has_anti_affinity || has_topology_spread
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_replicated_workloads_without_spreading_nor_pdb() {
+        let findings = analyze_file(Path::new("../tests/replica_resilience.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(3, findings.len());
+        assert_eq!("unprotected-deployment", findings[0].name());
+        assert_eq!("spreading,pdb", findings[0].lint_metadata()["missing"]);
+        assert_eq!("3", findings[0].lint_metadata()["replicas"]);
+        assert_eq!("deployment-without-pdb", findings[1].name());
+        assert_eq!("pdb", findings[1].lint_metadata()["missing"]);
+        assert_eq!("unspread-statefulset", findings[2].name());
+        assert_eq!("spreading", findings[2].lint_metadata()["missing"]);
+    }
+}
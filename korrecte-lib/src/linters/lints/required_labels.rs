@@ -1,11 +1,21 @@
-use crate::linters::Lint;
+use crate::linters::{KubeObjectType, Lint};
 
 use crate::f;
+use crate::kube::labels::{is_valid_key, is_valid_value};
+use crate::kube::namespace::namespace_matches;
 use crate::linters::evaluator::Context;
-use crate::reporting::Finding;
-use k8s_openapi::api::core::v1::Pod;
-use serde::Deserialize;
-use std::collections::HashMap;
+use crate::reporting::{fix, Finding, Patch};
+use crate::visitor::{pod_spec_visit, pod_template_pointer, PodSpecVisitor};
+use k8s_openapi::api::core::v1::{Pod, PodSpec};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+const LINT_NAME: &str = "required_labels";
+/// Value suggested for the missing labels and annotations, which has to be replaced by the user
+const PLACEHOLDER: &str = "changeme";
 
 pub(crate) struct RequiredLabels {
     config: Config,
@@ -19,7 +29,7 @@ impl RequiredLabels {
 
 impl Lint for RequiredLabels {
     fn name(&self) -> &str {
-        "required_labels"
+        LINT_NAME
     }
 
     fn core_v1_pod(&self, pod: &Pod, context: &Context) {
@@ -42,23 +52,284 @@ impl Lint for RequiredLabels {
                 format!("{:?}", missing_labels),
             );
 
-            let finding = Finding::new(self.name(), pod.metadata.clone()).with_metadata(metadata);
+            let missing: Vec<(&str, &str)> = missing_labels
+                .iter()
+                .map(|label| (label.as_str(), PLACEHOLDER))
+                .collect();
+            let fix = add_keys("/metadata", "labels", f!(pod.metadata, labels), &missing);
+            let finding = Finding::new(self.name(), pod.metadata.clone())
+                .with_metadata(metadata)
+                .with_unsafe_fix(fix);
 
             context.reporter.report(finding);
         }
     }
+
+    fn object(&self, object: &KubeObjectType, context: &Context) {
+        if let KubeObjectType::CoreV1Pod(pod) = object {
+            self.core_v1_pod(pod, context);
+        }
+
+        let checker = MetadataChecker {
+            config: &self.config,
+            kind: object.kind(),
+            meta: object.metadata(),
+            template_pointer: pod_template_pointer(object),
+            context,
+        };
+        if let Some(meta) = object.metadata() {
+            checker.check(meta, Location::Metadata);
+        }
+
+        pod_spec_visit(object, &mut PodTemplateVisitor { checker });
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Location {
+    Metadata,
+    PodTemplate,
+}
+
+impl Location {
+    fn as_str(self) -> &'static str {
+        match self {
+            Location::Metadata => "metadata",
+            Location::PodTemplate => "pod_template",
+        }
+    }
+}
+
+/// Checks the labels and annotations of an object, or of the pods it creates, against the
+/// Kubernetes grammar and the configured schemas
+struct MetadataChecker<'a> {
+    config: &'a Config,
+    kind: &'static str,
+    meta: Option<&'a ObjectMeta>,
+    template_pointer: Option<&'static str>,
+    context: &'a Context<'a>,
+}
+
+impl MetadataChecker<'_> {
+    fn check(&self, checked: &ObjectMeta, location: Location) {
+        let labels = checked.labels.as_ref();
+        let annotations = checked.annotations.as_ref();
+
+        for (key, value) in labels.into_iter().flatten() {
+            if !is_valid_key(key) {
+                self.report("invalid_key_syntax", "labels", key, location, |f| f);
+            }
+            if !is_valid_value(value) {
+                self.report("invalid_value_syntax", "labels", key, location, |f| {
+                    f.add_metadata("value", value)
+                });
+            }
+        }
+        for key in annotations.into_iter().flatten().map(|(key, _)| key) {
+            if !is_valid_key(key) {
+                self.report("invalid_key_syntax", "annotations", key, location, |f| f);
+            }
+        }
+
+        let namespace = f!(self.meta, namespace);
+        let schemas = self.config.schemas.iter().filter(|schema| {
+            (schema.kinds.is_empty() || schema.kinds.iter().any(|k| k == self.kind))
+                && namespace_matches(&schema.namespaces, namespace)
+        });
+
+        for schema in schemas {
+            self.check_rules(&schema.labels, labels, "labels", location);
+            self.check_rules(&schema.annotations, annotations, "annotations", location);
+            self.check_forbidden(&schema.forbidden_labels, labels, "labels", location);
+            self.check_forbidden(
+                &schema.forbidden_annotations,
+                annotations,
+                "annotations",
+                location,
+            );
+        }
+    }
+
+    fn check_rules(
+        &self,
+        rules: &[KeyRule],
+        current: Option<&BTreeMap<String, String>>,
+        field: &str,
+        location: Location,
+    ) {
+        for rule in rules {
+            match current.and_then(|current| current.get(&rule.key)) {
+                None if rule.required => {
+                    let fix = self.metadata_pointer(location).map(|pointer| {
+                        add_keys(
+                            &pointer,
+                            field,
+                            current,
+                            &[(rule.key.as_str(), rule.suggestion())],
+                        )
+                    });
+                    self.report("missing", field, &rule.key, location, |f| match fix {
+                        Some(fix) if rule.values.is_empty() => f.with_unsafe_fix(fix),
+                        Some(fix) => f.with_fix(fix),
+                        None => f,
+                    })
+                }
+                Some(value) if !rule.allows(value) => {
+                    self.report("invalid_value", field, &rule.key, location, |f| {
+                        f.add_metadata("value", value)
+                    })
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_forbidden(
+        &self,
+        forbidden: &[String],
+        current: Option<&BTreeMap<String, String>>,
+        field: &str,
+        location: Location,
+    ) {
+        for key in forbidden {
+            if current.map(|c| c.contains_key(key)).unwrap_or(false) {
+                self.report("forbidden", field, key, location, |f| f);
+            }
+        }
+    }
+
+    /// JSON pointer to the metadata checked on the given location
+    fn metadata_pointer(&self, location: Location) -> Option<String> {
+        match location {
+            Location::Metadata => Some("/metadata".to_string()),
+            Location::PodTemplate => self
+                .template_pointer
+                .map(|template| format!("{}/metadata", template)),
+        }
+    }
+
+    fn report<F>(&self, issue: &str, field: &str, key: &str, location: Location, extra: F)
+    where
+        F: FnOnce(Finding) -> Finding,
+    {
+        let finding = Finding::new(LINT_NAME, self.meta.cloned())
+            .add_metadata("issue", issue)
+            .add_metadata("field", field)
+            .add_metadata("key", key)
+            .add_metadata("location", location.as_str());
+
+        self.context.reporter.report(extra(finding));
+    }
+}
+
+/// Builds a patch adding the given keys to the labels or annotations of the metadata on `pointer`
+fn add_keys(
+    pointer: &str,
+    field: &str,
+    current: Option<&BTreeMap<String, String>>,
+    keys: &[(&str, &str)],
+) -> Patch {
+    let operations = match current {
+        Some(_) => keys
+            .iter()
+            .map(|(key, value)| fix::add(fix::pointer(pointer, vec![field, *key]), (*value).into()))
+            .collect(),
+        None => {
+            let map: serde_json::Map<String, Value> = keys
+                .iter()
+                .map(|(key, value)| (key.to_string(), (*value).into()))
+                .collect();
+            vec![fix::add(fix::pointer(pointer, vec![field]), map.into())]
+        }
+    };
+
+    Patch(operations)
+}
+
+struct PodTemplateVisitor<'a> {
+    checker: MetadataChecker<'a>,
+}
+
+impl PodSpecVisitor for PodTemplateVisitor<'_> {
+    fn visit_pod_spec(&mut self, _: &PodSpec, pod_meta: &ObjectMeta, meta: Option<&ObjectMeta>) {
+        // Bare pods are visited with their own metadata, which has already been checked
+        if !meta.map(|m| std::ptr::eq(m, pod_meta)).unwrap_or(false) {
+            self.checker.check(pod_meta, Location::PodTemplate);
+        }
+    }
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub(crate) struct Config {
+    /// Labels required on every pod
     #[serde(default = "default_labels")]
     labels: Vec<String>,
+    #[serde(default)]
+    schemas: Vec<Schema>,
+}
+
+/// Labels and annotations expected on the objects of the given kinds living on namespaces matching
+/// the given glob patterns. An empty list of kinds or namespaces matches all of them.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct Schema {
+    #[serde(default)]
+    kinds: Vec<String>,
+    #[serde(default)]
+    namespaces: Vec<String>,
+    #[serde(default)]
+    labels: Vec<KeyRule>,
+    #[serde(default)]
+    annotations: Vec<KeyRule>,
+    #[serde(default)]
+    forbidden_labels: Vec<String>,
+    #[serde(default)]
+    forbidden_annotations: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+struct KeyRule {
+    key: String,
+    /// If false, the value is only validated when the key is present
+    #[serde(default = "default_required")]
+    required: bool,
+    /// If not empty, list of allowed values
+    #[serde(default)]
+    values: Vec<String>,
+    /// Regular expression which has to match the whole value
+    #[serde(
+        default,
+        deserialize_with = "deserialize_pattern",
+        serialize_with = "serialize_pattern"
+    )]
+    pattern: Option<Regex>,
+}
+
+impl KeyRule {
+    /// Value suggested when the key is missing
+    fn suggestion(&self) -> &str {
+        self.values
+            .first()
+            .map(String::as_str)
+            .unwrap_or(PLACEHOLDER)
+    }
+
+    fn allows(&self, value: &str) -> bool {
+        let allowed_by_enum = self.values.is_empty() || self.values.iter().any(|v| v == value);
+        let allowed_by_pattern = self
+            .pattern
+            .as_ref()
+            .map(|pattern| pattern.is_match(value))
+            .unwrap_or(true);
+
+        allowed_by_enum && allowed_by_pattern
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
             labels: default_labels(),
+            schemas: Vec::new(),
         }
     }
 }
@@ -66,3 +337,163 @@ impl Default for Config {
 fn default_labels() -> Vec<String> {
     vec!["app".to_string(), "role".to_string()]
 }
+
+fn default_required() -> bool {
+    true
+}
+
+fn deserialize_pattern<'de, D>(deserializer: D) -> Result<Option<Regex>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let pattern = String::deserialize(deserializer)?;
+
+    Regex::new(&format!("^(?:{})$", pattern))
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+/// Serializes the pattern as it was written, without the anchors added when it was deserialized
+fn serialize_pattern<S>(pattern: &Option<Regex>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    pattern
+        .as_ref()
+        .map(|pattern| {
+            let pattern = pattern.as_str();
+            pattern
+                .strip_prefix("^(?:")
+                .and_then(|p| p.strip_suffix(")$"))
+                .unwrap_or(pattern)
+        })
+        .serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Config;
+    use crate::tests::{analyze_file_cfg, filter_findings_by};
+    use std::path::Path;
+
+    const SCHEMA: &str = r#"
+labels = []
+
+[[schemas]]
+kinds = ["Deployment"]
+namespaces = ["prod-*"]
+forbidden_labels = ["debug"]
+
+[[schemas.labels]]
+key = "team"
+values = ["payments", "search"]
+
+[[schemas.labels]]
+key = "version"
+required = false
+pattern = "v[0-9]+"
+
+[[schemas.annotations]]
+key = "owner"
+pattern = ".+@example\\.com"
+"#;
+
+    #[test]
+    fn it_validates_labels_against_the_schema() {
+        let config: Config = toml::from_str(SCHEMA).unwrap();
+        let global_config = crate::config::Config {
+            required_labels: config,
+            ..Default::default()
+        };
+
+        let findings = analyze_file_cfg(Path::new("../tests/label_schema.yaml"), global_config);
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+        let issues: Vec<(String, String, String, String)> = findings
+            .iter()
+            .map(|f| {
+                let metadata = f.lint_metadata();
+                (
+                    f.name().to_string(),
+                    metadata["issue"].clone(),
+                    metadata["key"].clone(),
+                    metadata["location"].clone(),
+                )
+            })
+            .collect();
+
+        let expected = vec![
+            ("payments", "missing", "owner", "metadata"),
+            ("payments", "invalid_value", "team", "pod_template"),
+            ("payments", "missing", "owner", "pod_template"),
+            ("search", "invalid_value", "version", "metadata"),
+            ("search", "forbidden", "debug", "metadata"),
+            ("search", "missing", "team", "pod_template"),
+            ("search", "missing", "owner", "pod_template"),
+            (
+                "bad-syntax",
+                "invalid_key_syntax",
+                "Example.com/team",
+                "metadata",
+            ),
+            ("bad-syntax", "invalid_value_syntax", "tier", "metadata"),
+        ];
+        let expected: Vec<(String, String, String, String)> = expected
+            .into_iter()
+            .map(|(a, b, c, d)| (a.to_string(), b.to_string(), c.to_string(), d.to_string()))
+            .collect();
+
+        assert_eq!(expected, issues);
+    }
+
+    #[test]
+    fn it_suggests_fixes_for_missing_keys() {
+        let findings =
+            analyze_file_cfg(Path::new("../tests/never_restart.yaml"), Default::default());
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(
+            r#"[{"op":"add","path":"/metadata/labels/role","value":"changeme"}]"#,
+            findings[0].fix().unwrap().to_string()
+        );
+        assert!(findings[0].is_fix_unsafe());
+
+        let config: Config = toml::from_str(SCHEMA).unwrap();
+        let global_config = crate::config::Config {
+            required_labels: config,
+            ..Default::default()
+        };
+
+        let findings = analyze_file_cfg(Path::new("../tests/label_schema.yaml"), global_config);
+        let missing: Vec<_> = filter_findings_by(findings, super::LINT_NAME)
+            .into_iter()
+            .filter(|f| f.lint_metadata()["issue"] == "missing")
+            .collect();
+        let fixes: Vec<String> = missing
+            .iter()
+            .map(|f| f.fix().unwrap().to_string())
+            .collect();
+
+        assert_eq!(
+            r#"[{"op":"add","path":"/metadata/annotations","value":{"owner":"changeme"}}]"#,
+            fixes[0]
+        );
+        assert_eq!(
+            r#"[{"op":"add","path":"/spec/template/metadata/labels/team","value":"payments"}]"#,
+            fixes[2]
+        );
+        assert!(missing[0].is_fix_unsafe());
+        assert!(!missing[2].is_fix_unsafe());
+    }
+
+    #[test]
+    fn it_rejects_invalid_patterns() {
+        let config = r#"
+[[schemas]]
+[[schemas.labels]]
+key = "team"
+pattern = "(unclosed"
+"#;
+
+        assert!(toml::from_str::<Config>(config).is_err());
+    }
+}
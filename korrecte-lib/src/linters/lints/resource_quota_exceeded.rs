@@ -0,0 +1,192 @@
+use crate::linters::{KubeObjectType, Lint};
+
+use crate::f;
+use crate::kube::limit_range::{effective_resources, EffectiveResources};
+use crate::kube::owner::is_controlled;
+use crate::kube::quantity::QuantityExt;
+use crate::kube::repository::ObjectRepository;
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use crate::visitor::{pod_spec_visit, PodSpecVisitor};
+use k8s_openapi::api::core::v1::{PodSpec, ResourceQuota};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use std::collections::BTreeMap;
+
+pub(crate) struct ResourceQuotaExceeded;
+
+const LINT_NAME: &str = "resource_quota_exceeded";
+const QUOTA_RESOURCES: [&str; 3] = ["cpu", "memory", "ephemeral-storage"];
+
+impl Lint for ResourceQuotaExceeded {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn core_v1_resource_quota(&self, quota: &ResourceQuota, context: &Context) {
+        // Scoped quotas only apply to a subset of pods (by priority class, QoS, ...), which can not
+        // be reliably computed from the declared objects
+        let is_scoped = f!(quota.spec, scopes)
+            .map(|s| !s.is_empty())
+            .unwrap_or(false)
+            || f!(quota.spec, scope_selector).is_some();
+        if is_scoped {
+            return;
+        }
+
+        let hard = match f!(quota.spec, hard) {
+            Some(hard) => hard,
+            None => return,
+        };
+        let usage = namespace_usage(context.repository, f!(quota.metadata, namespace));
+
+        for (resource, quantity) in hard.iter() {
+            let (hard, requested) = match (quantity.value(), usage.get(resource)) {
+                (Some(hard), Some(requested)) => (hard, *requested),
+                _ => continue,
+            };
+
+            if requested > hard {
+                let finding = Finding::new(LINT_NAME, quota.metadata.clone())
+                    .add_metadata("resource", resource.clone())
+                    .add_metadata("hard", hard)
+                    .add_metadata("requested", requested);
+                context.reporter.report(finding);
+            }
+        }
+    }
+}
+
+/// Computes the resources that all the workloads declared on the namespace would consume at
+/// their desired amount of replicas. The keys of the map follow the `ResourceQuota` naming
+/// (`pods`, `requests.cpu`, `limits.memory`, ...).
+fn namespace_usage(
+    repository: &dyn ObjectRepository,
+    namespace: Option<&String>,
+) -> BTreeMap<String, f64> {
+    let mut visitor = UsageVisitor {
+        repository,
+        replicas: 0.0,
+        usage: BTreeMap::new(),
+    };
+
+    for object in repository.iter() {
+        let meta = object.metadata();
+        if f!(meta, namespace) != namespace || is_controlled(meta) {
+            continue;
+        }
+
+        if let Some(replicas) = desired_replicas(repository, object) {
+            visitor.replicas = replicas;
+            pod_spec_visit(object, &mut visitor);
+        }
+    }
+
+    visitor.usage
+}
+
+fn desired_replicas(repository: &dyn ObjectRepository, object: &KubeObjectType) -> Option<f64> {
+    let replicas = match object {
+        KubeObjectType::CoreV1Pod(_) => 1,
+        KubeObjectType::AppsV1Deployment(deployment) => {
+            f!(deployment.spec, replicas).cloned().unwrap_or(1)
+        }
+        KubeObjectType::AppsV1ReplicaSet(replica_set) => {
+            f!(replica_set.spec, replicas).cloned().unwrap_or(1)
+        }
+        KubeObjectType::AppsV1StatefulSet(stateful_set) => {
+            f!(stateful_set.spec, replicas).cloned().unwrap_or(1)
+        }
+        // Daemon sets run a pod on each node. If nodes are not known, we assume a single one
+        KubeObjectType::AppsV1DaemonSet(_) => repository
+            .iter()
+            .filter(|o| matches!(o, KubeObjectType::CoreV1Node(_)))
+            .count()
+            .max(1) as i32,
+        _ => return None,
+    };
+
+    Some(f64::from(replicas))
+}
+
+struct UsageVisitor<'a> {
+    repository: &'a dyn ObjectRepository,
+    replicas: f64,
+    usage: BTreeMap<String, f64>,
+}
+
+impl PodSpecVisitor for UsageVisitor<'_> {
+    fn visit_pod_spec(&mut self, pod_spec: &PodSpec, _: &ObjectMeta, meta: Option<&ObjectMeta>) {
+        let namespace = f!(meta, namespace);
+        let containers: Vec<_> = pod_spec
+            .containers
+            .iter()
+            .map(|c| effective_resources(self.repository, namespace, c))
+            .collect();
+        let init_containers: Vec<_> = pod_spec
+            .init_containers
+            .iter()
+            .flatten()
+            .map(|c| effective_resources(self.repository, namespace, c))
+            .collect();
+
+        *self.usage.entry("pods".to_string()).or_insert(0.0) += self.replicas;
+
+        for resource in QUOTA_RESOURCES.iter() {
+            let request = pod_amount(&containers, &init_containers, |c| {
+                c.requests.get(*resource).and_then(|q| q.value())
+            });
+            let limit = pod_amount(&containers, &init_containers, |c| {
+                c.limits.get(*resource).and_then(|q| q.value())
+            });
+
+            // Both `cpu` and `requests.cpu` refer to the requested amount
+            for key in &[resource.to_string(), format!("requests.{}", resource)] {
+                *self.usage.entry(key.clone()).or_insert(0.0) += request * self.replicas;
+            }
+            *self
+                .usage
+                .entry(format!("limits.{}", resource))
+                .or_insert(0.0) += limit * self.replicas;
+        }
+    }
+}
+
+/// Init containers run sequentially before the regular containers, so a pod is accounted with
+/// the maximum between the sum of its containers and its biggest init container
+fn pod_amount<F>(
+    containers: &[EffectiveResources],
+    init_containers: &[EffectiveResources],
+    amount: F,
+) -> f64
+where
+    F: Fn(&EffectiveResources) -> Option<f64>,
+{
+    let regular: f64 = containers.iter().filter_map(&amount).sum();
+    let init = init_containers
+        .iter()
+        .filter_map(&amount)
+        .fold(0.0, f64::max);
+
+    regular.max(init)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_namespaces_whose_workloads_exceed_the_quota() {
+        let findings = analyze_file(Path::new("../tests/resource_quota.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(2, findings.len());
+        assert_eq!("small-quota", findings[0].name());
+        assert_eq!("requests.cpu", findings[0].lint_metadata()["resource"]);
+        assert_eq!("1", findings[0].lint_metadata()["hard"]);
+        assert_eq!("1.5", findings[0].lint_metadata()["requested"]);
+        assert_eq!("small-quota", findings[1].name());
+        assert_eq!("requests.memory", findings[1].lint_metadata()["resource"]);
+        assert_eq!("1342177280", findings[1].lint_metadata()["requested"]);
+    }
+}
@@ -0,0 +1,215 @@
+use crate::linters::Lint;
+
+use crate::kube::service::selecting_services;
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use crate::{f, m};
+use k8s_openapi::api::apps::v1::{Deployment, RollingUpdateDeployment, StatefulSet};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+
+pub(crate) struct RolloutStrategy;
+
+const LINT_NAME: &str = "rollout_strategy";
+const RECREATE_STRATEGY: &str = "Recreate";
+const DEFAULT_ROLLING_VALUE: &str = "25%";
+
+impl Lint for RolloutStrategy {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn apps_v1_deployment(&self, deployment: &Deployment, context: &Context) {
+        let spec = match deployment.spec.as_ref() {
+            Some(spec) => spec,
+            None => return,
+        };
+        let meta = deployment.metadata.as_ref();
+        let replicas = spec.replicas.unwrap_or(1);
+        let strategy_type = f!(spec.strategy, type_).map(|t| t.as_str());
+
+        if strategy_type == Some(RECREATE_STRATEGY) {
+            self.check_recreate(deployment, context);
+        } else {
+            let default = RollingUpdateDeployment::default();
+            let rolling_update = f!(spec.strategy, rolling_update).unwrap_or(&default);
+            self.check_rolling_update(meta, rolling_update, replicas, context);
+        }
+
+        if let Some(min_ready_seconds) = spec.min_ready_seconds.filter(|s| *s > 0) {
+            self.check_min_ready_seconds(deployment, min_ready_seconds, context);
+        }
+    }
+
+    fn apps_v1_stateful_set(&self, stateful_set: &StatefulSet, context: &Context) {
+        let spec = match stateful_set.spec.as_ref() {
+            Some(spec) => spec,
+            None => return,
+        };
+        let replicas = spec.replicas.unwrap_or(1);
+
+        // `maxUnavailable` is not available for stateful sets on the supported api version, so the
+        // only way of stalling a rolling update is a partition which leaves out all the replicas
+        if let Some(partition) = f!(spec.update_strategy, rolling_update, partition) {
+            if *partition >= replicas && replicas > 0 {
+                let finding = self
+                    .finding(stateful_set.metadata.as_ref(), "partition_blocks_rollout")
+                    .add_metadata("partition", partition)
+                    .add_metadata("replicas", replicas);
+                context.reporter.report(finding);
+            }
+        }
+    }
+}
+
+impl RolloutStrategy {
+    fn check_rolling_update(
+        &self,
+        meta: Option<&ObjectMeta>,
+        rolling_update: &RollingUpdateDeployment,
+        replicas: i32,
+        context: &Context,
+    ) {
+        let default = IntOrString::String(DEFAULT_ROLLING_VALUE.to_string());
+        let max_surge = rolling_update.max_surge.as_ref().unwrap_or(&default);
+        let max_unavailable = rolling_update.max_unavailable.as_ref().unwrap_or(&default);
+
+        if is_zero(max_surge) && is_zero(max_unavailable) {
+            let finding = self.finding(meta, "rollout_can_not_progress");
+            context.reporter.report(finding);
+            return;
+        }
+
+        // Surge is rounded up and unavailability is rounded down by the deployment controller.
+        // Only explicit values are checked, as the defaults are fine for any amount of replicas.
+        let fields = [
+            ("maxSurge", rolling_update.max_surge.as_ref(), true),
+            (
+                "maxUnavailable",
+                rolling_update.max_unavailable.as_ref(),
+                false,
+            ),
+        ];
+        for (field, value, round_up) in fields.iter() {
+            if let Some(IntOrString::String(percent)) = value {
+                let scaled = scaled_value(percent, replicas, *round_up);
+
+                if percent != "0%" && scaled == Some(0) {
+                    let finding = self
+                        .finding(meta, "percentage_rounds_to_zero")
+                        .add_metadata("field", field)
+                        .add_metadata("value", percent)
+                        .add_metadata("replicas", replicas);
+                    context.reporter.report(finding);
+                }
+            }
+        }
+    }
+
+    fn check_recreate(&self, deployment: &Deployment, context: &Context) {
+        let meta = deployment.metadata.as_ref();
+        let pod_labels = m!(deployment.spec, template).and_then(|t| f!(t.metadata, labels));
+
+        for service in selecting_services(context.repository, f!(meta, namespace), pod_labels) {
+            let finding = self.finding(meta, "recreate_behind_service").add_metadata(
+                "service",
+                f!(service.metadata, name).cloned().unwrap_or_default(),
+            );
+            context.reporter.report(finding);
+        }
+    }
+
+    fn check_min_ready_seconds(
+        &self,
+        deployment: &Deployment,
+        min_ready_seconds: i32,
+        context: &Context,
+    ) {
+        let containers = m!(deployment.spec, template)
+            .and_then(|t| m!(t.spec, containers))
+            .cloned()
+            .unwrap_or_default();
+
+        for container in containers.iter() {
+            let initial_delay = f!(container.readiness_probe, initial_delay_seconds)
+                .cloned()
+                .unwrap_or(0);
+
+            if min_ready_seconds < initial_delay {
+                let finding = self
+                    .finding(
+                        deployment.metadata.as_ref(),
+                        "min_ready_seconds_below_readiness_delay",
+                    )
+                    .add_metadata("container", &container.name)
+                    .add_metadata("min_ready_seconds", min_ready_seconds)
+                    .add_metadata("initial_delay_seconds", initial_delay);
+                context.reporter.report(finding);
+            }
+        }
+    }
+
+    fn finding(&self, meta: Option<&ObjectMeta>, issue: &str) -> Finding {
+        Finding::new(LINT_NAME, meta.cloned()).add_metadata("issue", issue)
+    }
+}
+
+fn is_zero(value: &IntOrString) -> bool {
+    match value {
+        IntOrString::Int(amount) => *amount == 0,
+        IntOrString::String(percent) => percent == "0%",
+    }
+}
+
+fn scaled_value(percent: &str, total: i32, round_up: bool) -> Option<i32> {
+    let percent = percent.strip_suffix('%')?.parse::<i32>().ok()?;
+
+    if round_up {
+        Some((percent * total + 99) / 100)
+    } else {
+        Some(percent * total / 100)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_rollouts_which_can_stall() {
+        let findings = analyze_file(Path::new("../tests/rollout_strategy.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(5, findings.len());
+
+        assert_eq!("no-surge-no-unavailable", findings[0].name());
+        assert_eq!(
+            "rollout_can_not_progress",
+            findings[0].lint_metadata()["issue"]
+        );
+
+        assert_eq!("rounds-to-zero", findings[1].name());
+        assert_eq!(
+            "percentage_rounds_to_zero",
+            findings[1].lint_metadata()["issue"]
+        );
+        assert_eq!("maxUnavailable", findings[1].lint_metadata()["field"]);
+        assert_eq!("10%", findings[1].lint_metadata()["value"]);
+        assert_eq!("3", findings[1].lint_metadata()["replicas"]);
+
+        assert_eq!("recreate-with-service", findings[2].name());
+        assert_eq!("frontend", findings[2].lint_metadata()["service"]);
+
+        assert_eq!("slow-readiness", findings[3].name());
+        assert_eq!("app", findings[3].lint_metadata()["container"]);
+        assert_eq!("5", findings[3].lint_metadata()["min_ready_seconds"]);
+        assert_eq!("30", findings[3].lint_metadata()["initial_delay_seconds"]);
+
+        assert_eq!("partitioned-statefulset", findings[4].name());
+        assert_eq!(
+            "partition_blocks_rollout",
+            findings[4].lint_metadata()["issue"]
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use crate::linters::Lint;
+
+use crate::kube::label_selector::LabelSelectorExt;
+use crate::kube::owner::is_controlled;
+use crate::linters::evaluator::Context;
+use crate::linters::KubeObjectType;
+use crate::reporting::Finding;
+use crate::{f, m};
+use k8s_openapi::api::core::v1::PodTemplateSpec;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta};
+use std::collections::BTreeMap;
+
+pub(crate) struct SelectorOverlap;
+
+const LINT_NAME: &str = "selector_overlap";
+
+/// Pod controller along with its selector and the labels of the pods it creates
+struct Controller<'a> {
+    kind: &'static str,
+    meta: Option<&'a ObjectMeta>,
+    selector: Option<&'a LabelSelector>,
+    pod_labels: Option<&'a BTreeMap<String, String>>,
+}
+
+impl<'a> Controller<'a> {
+    fn new(
+        kind: &'static str,
+        meta: Option<&'a ObjectMeta>,
+        selector: Option<&'a LabelSelector>,
+        template: Option<&'a PodTemplateSpec>,
+    ) -> Self {
+        Controller {
+            kind,
+            meta,
+            selector,
+            pod_labels: template.and_then(|t| f!(t.metadata, labels)),
+        }
+    }
+
+    fn from_object(object: &'a KubeObjectType) -> Option<Self> {
+        match object {
+            KubeObjectType::AppsV1DaemonSet(o) => Some(Controller::new(
+                "DaemonSet",
+                o.metadata.as_ref(),
+                m!(o.spec, selector),
+                m!(o.spec, template),
+            )),
+            KubeObjectType::AppsV1Deployment(o) => Some(Controller::new(
+                "Deployment",
+                o.metadata.as_ref(),
+                m!(o.spec, selector),
+                m!(o.spec, template),
+            )),
+            KubeObjectType::AppsV1ReplicaSet(o) => Some(Controller::new(
+                "ReplicaSet",
+                o.metadata.as_ref(),
+                m!(o.spec, selector),
+                f!(o.spec, template),
+            )),
+            KubeObjectType::AppsV1StatefulSet(o) => Some(Controller::new(
+                "StatefulSet",
+                o.metadata.as_ref(),
+                m!(o.spec, selector),
+                m!(o.spec, template),
+            )),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> Option<&String> {
+        f!(self.meta, name)
+    }
+
+    fn namespace(&self) -> Option<&String> {
+        f!(self.meta, namespace)
+    }
+
+    /// Identifies the controller with the format `Kind/name`
+    fn reference(&self) -> String {
+        format!(
+            "{}/{}",
+            self.kind,
+            self.name().map(|n| n.as_str()).unwrap_or("")
+        )
+    }
+
+    /// Checks if the selector of the controller matches the given pod labels. Empty selectors are
+    /// rejected by the api server for these kinds, so they are considered as not matching anything.
+    fn selects(&self, pod_labels: Option<&BTreeMap<String, String>>) -> bool {
+        self.selector
+            .map(|selector| !selector.is_empty() && selector.matches(pod_labels))
+            .unwrap_or(false)
+    }
+}
+
+impl Lint for SelectorOverlap {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn object(&self, object: &KubeObjectType, context: &Context) {
+        let controller = match Controller::from_object(object) {
+            Some(controller) => controller,
+            None => return,
+        };
+
+        if !controller.selects(controller.pod_labels) {
+            let finding = Finding::new(LINT_NAME, controller.meta.cloned())
+                .add_metadata("issue", "selector_does_not_match_template");
+            context.reporter.report(finding);
+        }
+
+        // Objects managed by other controllers (like the replica sets of a deployment) share the
+        // pods with their owner, so they are not taken into account
+        if is_controlled(controller.meta) {
+            return;
+        }
+
+        let overlapping = context
+            .repository
+            .iter()
+            .filter(|other| !is_controlled(other.metadata()))
+            .filter_map(Controller::from_object)
+            .filter(|other| other.namespace() == controller.namespace())
+            .filter(|other| other.kind != controller.kind || other.name() != controller.name())
+            .filter(|other| {
+                controller.selects(other.pod_labels) || other.selects(controller.pod_labels)
+            });
+
+        for other in overlapping {
+            let finding = Finding::new(LINT_NAME, controller.meta.cloned())
+                .add_metadata("issue", "overlapping_selector")
+                .add_metadata("overlapping", other.reference());
+            context.reporter.report(finding);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_overlapping_selectors() {
+        let findings = analyze_file(Path::new("../tests/selector_overlap.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(5, findings.len());
+
+        assert_eq!("frontend", findings[0].name());
+        assert_eq!("overlapping_selector", findings[0].lint_metadata()["issue"]);
+        assert_eq!(
+            "ReplicaSet/frontend-canary",
+            findings[0].lint_metadata()["overlapping"]
+        );
+
+        assert_eq!("frontend-canary", findings[1].name());
+        assert_eq!(
+            "Deployment/frontend",
+            findings[1].lint_metadata()["overlapping"]
+        );
+
+        assert_eq!("mismatched", findings[2].name());
+        assert_eq!(
+            "selector_does_not_match_template",
+            findings[2].lint_metadata()["issue"]
+        );
+
+        assert_eq!("backend", findings[3].name());
+        assert_eq!(
+            "Deployment/expressions",
+            findings[3].lint_metadata()["overlapping"]
+        );
+        assert_eq!("expressions", findings[4].name());
+        assert_eq!(
+            "StatefulSet/backend",
+            findings[4].lint_metadata()["overlapping"]
+        );
+    }
+}
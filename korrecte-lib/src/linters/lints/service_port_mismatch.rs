@@ -0,0 +1,118 @@
+use crate::linters::Lint;
+
+use crate::f;
+use crate::kube::service::{FindAllMatchingPodSpecs, MatchingPodSpec};
+use crate::linters::evaluator::Context;
+use crate::reporting::Finding;
+use crate::visitor::visit_all_pod_specs;
+use k8s_openapi::api::core::v1::{Service, ServicePort};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use std::collections::BTreeMap;
+
+const LINT_NAME: &str = "service_port_mismatch";
+const DEFAULT_PROTOCOL: &str = "TCP";
+
+pub(crate) struct ServicePortMismatch;
+
+impl Lint for ServicePortMismatch {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn core_v1_service(&self, service: &Service, context: &Context) {
+        let selectors: BTreeMap<String, String> =
+            f!(service.spec, selector).cloned().unwrap_or_default();
+
+        // Services without selectors are backed by manually managed endpoints
+        if selectors.is_empty() {
+            return;
+        }
+
+        let mut visitor = FindAllMatchingPodSpecs::new(&selectors, f!(service.metadata, namespace));
+        visit_all_pod_specs(context, &mut visitor);
+
+        for service_port in f!(service.spec, ports).unwrap_or(&vec![]) {
+            for matching in visitor.matching_pod_specs() {
+                if !Self::pod_spec_exposes_port(matching, service_port) {
+                    Self::report(service, service_port, matching, context);
+                }
+            }
+        }
+    }
+}
+
+impl ServicePortMismatch {
+    fn pod_spec_exposes_port(matching: &MatchingPodSpec, service_port: &ServicePort) -> bool {
+        let target_port = Self::target_port(service_port);
+        let protocol = service_port.protocol.as_deref().unwrap_or(DEFAULT_PROTOCOL);
+
+        matching
+            .pod_spec
+            .containers
+            .iter()
+            .flat_map(|container| container.ports.iter().flatten())
+            .any(|container_port| {
+                let matches_port = match target_port {
+                    IntOrString::Int(number) => container_port.container_port == number,
+                    IntOrString::String(ref name) => container_port.name.as_ref() == Some(name),
+                };
+                let container_protocol = container_port
+                    .protocol
+                    .as_deref()
+                    .unwrap_or(DEFAULT_PROTOCOL);
+
+                matches_port && container_protocol.eq_ignore_ascii_case(protocol)
+            })
+    }
+
+    fn target_port(service_port: &ServicePort) -> IntOrString {
+        service_port
+            .target_port
+            .clone()
+            .unwrap_or(IntOrString::Int(service_port.port))
+    }
+
+    fn report(
+        service: &Service,
+        service_port: &ServicePort,
+        matching: &MatchingPodSpec,
+        context: &Context,
+    ) {
+        let target_port = match Self::target_port(service_port) {
+            IntOrString::Int(number) => number.to_string(),
+            IntOrString::String(name) => name,
+        };
+
+        let finding = Finding::new(LINT_NAME, service.metadata.clone())
+            .add_metadata("port", service_port.port)
+            .add_metadata("target_port", target_port)
+            .add_metadata(
+                "protocol",
+                service_port.protocol.as_deref().unwrap_or(DEFAULT_PROTOCOL),
+            )
+            .add_metadata("pod_owner", matching.owner.clone().unwrap_or_default());
+
+        context.reporter.report(finding);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_target_ports_not_exposed_by_pods() {
+        let findings = analyze_file(Path::new("../tests/service_port_mismatch.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(3, findings.len());
+        assert_eq!("service-unknown-named-port", findings[0].name());
+        assert_eq!("metrics", findings[0].lint_metadata()["target_port"]);
+        assert_eq!("service-unknown-numeric-port", findings[1].name());
+        assert_eq!("9999", findings[1].lint_metadata()["target_port"]);
+        assert_eq!("service-protocol-mismatch", findings[2].name());
+        assert_eq!("UDP", findings[2].lint_metadata()["protocol"]);
+        assert_eq!("web-deployment", findings[2].lint_metadata()["pod_owner"]);
+    }
+}
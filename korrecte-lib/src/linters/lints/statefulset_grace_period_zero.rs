@@ -1,11 +1,13 @@
 use crate::linters::Lint;
 
 use crate::linters::evaluator::Context;
-use crate::reporting::Finding;
+use crate::reporting::{fix, Finding, Patch};
 use crate::{f, m};
 use k8s_openapi::api::apps::v1::StatefulSet;
 
 const LINT_NAME: &str = "statefulset_no_grace_period";
+/// Grace period applied by Kubernetes when it is not specified
+const DEFAULT_GRACE_PERIOD: i64 = 30;
 
 #[derive(Default)]
 pub(crate) struct StatefulsetGracePeriodZero;
@@ -22,7 +24,12 @@ impl Lint for StatefulsetGracePeriodZero {
                 .unwrap_or(1);
 
             if grace_period == 0 {
-                let finding = Finding::new(self.name(), stateful_set.metadata.clone());
+                let operation = fix::replace(
+                    "/spec/template/spec/terminationGracePeriodSeconds".to_string(),
+                    DEFAULT_GRACE_PERIOD.into(),
+                );
+                let finding = Finding::new(self.name(), stateful_set.metadata.clone())
+                    .with_fix(Patch(vec![operation]));
                 context.reporter.report(finding);
             }
         }
@@ -42,5 +49,9 @@ mod test {
         assert_eq!(1, findings.len());
         assert_eq!(findings[0].lint_name(), super::LINT_NAME);
         assert_eq!(findings[0].name(), "web");
+        assert_eq!(
+            r#"[{"op":"replace","path":"/spec/template/spec/terminationGracePeriodSeconds","value":30}]"#,
+            findings[0].fix().unwrap().to_string()
+        );
     }
 }
@@ -0,0 +1,173 @@
+use crate::linters::Lint;
+
+use crate::kube::pdb::matching_pdbs;
+use crate::linters::evaluator::Context;
+use crate::linters::KubeObjectType;
+use crate::reporting::Finding;
+use crate::{f, m};
+use k8s_openapi::api::apps::v1::{StatefulSet, StatefulSetSpec};
+use k8s_openapi::api::core::v1::Service;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+pub(crate) struct StatefulsetMisconfiguration;
+
+const LINT_NAME: &str = "statefulset_misconfiguration";
+const HEADLESS_CLUSTER_IP: &str = "None";
+const PARALLEL_POLICY: &str = "Parallel";
+const ON_DELETE_STRATEGY: &str = "OnDelete";
+
+impl Lint for StatefulsetMisconfiguration {
+    fn name(&self) -> &str {
+        LINT_NAME
+    }
+
+    fn apps_v1_stateful_set(&self, stateful_set: &StatefulSet, context: &Context) {
+        let spec = match stateful_set.spec.as_ref() {
+            Some(spec) => spec,
+            None => return,
+        };
+        let meta = stateful_set.metadata.as_ref();
+
+        self.check_service(meta, spec, context);
+        self.check_storage_classes(meta, spec, context);
+        self.check_parallel_with_pdb(meta, spec, context);
+
+        if f!(spec.update_strategy, type_).map(|t| t.as_str()) == Some(ON_DELETE_STRATEGY) {
+            let finding = self.finding(meta, "on_delete_strategy");
+            context.reporter.report(finding);
+        }
+    }
+}
+
+impl StatefulsetMisconfiguration {
+    fn check_service(&self, meta: Option<&ObjectMeta>, spec: &StatefulSetSpec, context: &Context) {
+        let namespace = f!(meta, namespace);
+        let service = context.repository.iter().find_map(|object| match object {
+            KubeObjectType::CoreV1Service(service)
+                if f!(service.metadata, name) == Some(&spec.service_name)
+                    && f!(service.metadata, namespace) == namespace =>
+            {
+                Some(service.as_ref())
+            }
+            _ => None,
+        });
+
+        let issue = match service {
+            None => "missing_service",
+            Some(service) if !Self::is_headless(service) => "service_not_headless",
+            _ => return,
+        };
+
+        let finding = self
+            .finding(meta, issue)
+            .add_metadata("service", &spec.service_name);
+        context.reporter.report(finding);
+    }
+
+    /// Storage classes are only checked when some of them have been loaded. Otherwise, all the
+    /// classes would be reported when analyzing files without the cluster wide objects.
+    fn check_storage_classes(
+        &self,
+        meta: Option<&ObjectMeta>,
+        spec: &StatefulSetSpec,
+        context: &Context,
+    ) {
+        let storage_classes: Vec<&String> = context
+            .repository
+            .iter()
+            .filter_map(|object| match object {
+                KubeObjectType::StorageV1StorageClass(class) => f!(class.metadata, name),
+                _ => None,
+            })
+            .collect();
+
+        if storage_classes.is_empty() {
+            return;
+        }
+
+        for claim in spec.volume_claim_templates.iter().flatten() {
+            // An empty class disables the dynamic provisioning, so it is not a reference
+            let class = match f!(claim.spec, storage_class_name) {
+                Some(class) if !class.is_empty() => class,
+                _ => continue,
+            };
+
+            if !storage_classes.contains(&class) {
+                let finding = self
+                    .finding(meta, "unknown_storage_class")
+                    .add_metadata(
+                        "volume_claim",
+                        f!(claim.metadata, name).cloned().unwrap_or_default(),
+                    )
+                    .add_metadata("storage_class", class);
+                context.reporter.report(finding);
+            }
+        }
+    }
+
+    fn check_parallel_with_pdb(
+        &self,
+        meta: Option<&ObjectMeta>,
+        spec: &StatefulSetSpec,
+        context: &Context,
+    ) {
+        if spec.pod_management_policy.as_deref() != Some(PARALLEL_POLICY) {
+            return;
+        }
+
+        let pod_labels = f!(spec.template.metadata, labels);
+        for pdb in matching_pdbs(context.repository, f!(meta, namespace), pod_labels) {
+            let finding = self
+                .finding(meta, "parallel_with_pdb")
+                .add_metadata("pdb", f!(pdb.metadata, name).cloned().unwrap_or_default());
+            context.reporter.report(finding);
+        }
+    }
+
+    fn is_headless(service: &Service) -> bool {
+        m!(service.spec, cluster_ip)
+            .map(|ip| ip.as_deref() == Some(HEADLESS_CLUSTER_IP))
+            .unwrap_or(false)
+    }
+
+    fn finding(&self, meta: Option<&ObjectMeta>, issue: &str) -> Finding {
+        Finding::new(LINT_NAME, meta.cloned()).add_metadata("issue", issue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyze_file, filter_findings_by};
+    use std::path::Path;
+
+    #[test]
+    fn it_finds_misconfigured_statefulsets() {
+        let findings = analyze_file(Path::new("../tests/statefulset_misconfiguration.yaml"));
+        let findings = filter_findings_by(findings, super::LINT_NAME);
+
+        assert_eq!(5, findings.len());
+
+        assert_eq!("missing-service", findings[0].name());
+        assert_eq!("missing_service", findings[0].lint_metadata()["issue"]);
+        assert_eq!("does-not-exist", findings[0].lint_metadata()["service"]);
+
+        assert_eq!("not-headless", findings[1].name());
+        assert_eq!("service_not_headless", findings[1].lint_metadata()["issue"]);
+        assert_eq!("not-headless", findings[1].lint_metadata()["service"]);
+
+        assert_eq!("unknown-class", findings[2].name());
+        assert_eq!(
+            "unknown_storage_class",
+            findings[2].lint_metadata()["issue"]
+        );
+        assert_eq!("data", findings[2].lint_metadata()["volume_claim"]);
+        assert_eq!("premium", findings[2].lint_metadata()["storage_class"]);
+
+        assert_eq!("parallel-with-pdb", findings[3].name());
+        assert_eq!("parallel_with_pdb", findings[3].lint_metadata()["issue"]);
+        assert_eq!("parallel-pdb", findings[3].lint_metadata()["pdb"]);
+
+        assert_eq!("on-delete", findings[4].name());
+        assert_eq!("on_delete_strategy", findings[4].lint_metadata()["issue"]);
+    }
+}
@@ -3,11 +3,15 @@ use crate::linters;
 use anyhow::*;
 use serde::{Deserialize, Serialize};
 
+pub(crate) mod custom;
 pub mod evaluator;
 mod lint;
 pub(crate) mod lints;
+pub(crate) mod plugins;
+pub(crate) mod scripting;
 pub use lint::{KubeObjectType, Lint};
 use std::collections::HashMap;
+use std::fmt;
 
 #[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub enum Group {
@@ -26,11 +30,33 @@ impl ToString for Group {
     }
 }
 
+#[derive(Clone, Eq, PartialEq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    #[default]
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => f.write_str("info"),
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub struct LintSpec {
     pub group: Group,
     pub name: String,
+    #[serde(default)]
+    pub severity: Severity,
     pub description: String,
+    #[serde(default)]
     pub references: Vec<String>,
 }
 
@@ -56,6 +82,30 @@ impl LintSpecLoader {
         Ok(LintSpecLoader { specs: lint_def })
     }
 
+    /// Loads the specs of the built-in lints along with the specs of the custom rules, scripts and
+    /// plugins defined on the configuration
+    pub fn with_config(cfg: &Config) -> Result<LintSpecLoader> {
+        let mut loader = Self::new()?;
+
+        let custom_specs = cfg
+            .custom_rules
+            .rules()
+            .iter()
+            .map(|rule| rule.spec())
+            .chain(cfg.scripts.scripts().iter().map(|s| s.spec().clone()))
+            .chain(cfg.plugins.plugins().iter().map(|p| p.spec().clone()));
+
+        for spec in custom_specs {
+            if loader.specs.contains_key(&spec.name) {
+                return Err(anyhow!("Duplicated lint name: {}", spec.name));
+            }
+
+            loader.specs.insert(spec.name.clone(), spec);
+        }
+
+        Ok(loader)
+    }
+
     pub fn get(&self, name: &str) -> Option<&LintSpec> {
         self.specs.get(name)
     }
@@ -76,34 +126,106 @@ impl LintCollection {
         let passwords = linters::lints::environment_passwords::EnvironmentPasswords::new(
             cfg.environment_passwords.clone(),
         );
+        let hardcoded_secrets =
+            linters::lints::hardcoded_secrets::HardcodedSecrets::new(cfg.hardcoded_secrets.clone());
+        let hpa_misconfiguration = linters::lints::hpa_misconfiguration::HpaMisconfiguration {};
         let hpa_no_request = linters::lints::hpa_no_request::HpaNoRequest::default();
+        let ingress_backend =
+            linters::lints::ingress_backend_validation::IngressBackendValidation {};
+        let missing_probes = linters::lints::missing_probes::MissingProbes {};
+        let network_default_deny =
+            linters::lints::network_policy_default_deny::NetworkPolicyDefaultDeny {};
+        let network_uncovered =
+            linters::lints::network_policy_uncovered_pods::NetworkPolicyUncoveredPods {};
+        let network_unused = linters::lints::network_policy_unused::NetworkPolicyUnused {};
         let never = linters::lints::never_restart_with_liveness_probe::NeverRestartWithLivenessProbe::default();
         let overlapping = linters::lints::overlapping_probes::OverlappingProbes::default();
         let pdb_min = linters::lints::pdb_min_replicas::PdbMinReplicas {};
-        let pod_requirements = linters::lints::pod_requirements::PodRequirements::default();
+        let pdb_overlapping = linters::lints::pdb_overlapping::PdbOverlapping {};
+        let pod_requirements =
+            linters::lints::pod_requirements::PodRequirements::new(cfg.pod_requirements.clone());
+        let probe_misconfiguration =
+            linters::lints::probe_misconfiguration::ProbeMisconfiguration {};
+        let rbac_escalation = linters::lints::rbac_privilege_escalation::RbacPrivilegeEscalation {};
+        let rbac_secrets = linters::lints::rbac_secrets_access::RbacSecretsAccess {};
+        let rbac_wildcard = linters::lints::rbac_wildcard_permissions::RbacWildcardPermissions {};
+        let replica_resilience = linters::lints::replica_resilience::ReplicaResilience {};
         let required =
             linters::lints::required_labels::RequiredLabels::new(cfg.required_labels.clone());
+        let resource_quota = linters::lints::resource_quota_exceeded::ResourceQuotaExceeded {};
         let role_similar = linters::lints::role_similar_names::RoleSimilarNames {};
+        let rollout_strategy = linters::lints::rollout_strategy::RolloutStrategy {};
+        let selector_overlap = linters::lints::selector_overlap::SelectorOverlap {};
+        let service_port_mismatch = linters::lints::service_port_mismatch::ServicePortMismatch {};
         let service_target_port = linters::lints::service_target_port::ServiceTargetPort {};
         let service_labels =
             linters::lints::service_without_matching_labels::ServiceWithoutMatchingLabels {};
         let statefulset_grace_period_zero =
             linters::lints::statefulset_grace_period_zero::StatefulsetGracePeriodZero::default();
+        let statefulset_misconfiguration =
+            linters::lints::statefulset_misconfiguration::StatefulsetMisconfiguration {};
 
-        vec![
+        let mut lints: LintList<'a> = vec![
             Box::new(alb_ingress),
             Box::new(alb_named_sg),
             Box::new(passwords),
+            Box::new(hardcoded_secrets),
+            Box::new(hpa_misconfiguration),
             Box::new(hpa_no_request),
+            Box::new(ingress_backend),
+            Box::new(missing_probes),
+            Box::new(network_default_deny),
+            Box::new(network_uncovered),
+            Box::new(network_unused),
             Box::new(never),
             Box::new(overlapping),
             Box::new(pdb_min),
+            Box::new(pdb_overlapping),
             Box::new(pod_requirements),
+            Box::new(probe_misconfiguration),
+            Box::new(rbac_escalation),
+            Box::new(rbac_secrets),
+            Box::new(rbac_wildcard),
+            Box::new(replica_resilience),
             Box::new(required),
+            Box::new(resource_quota),
             Box::new(role_similar),
+            Box::new(rollout_strategy),
+            Box::new(selector_overlap),
+            Box::new(service_port_mismatch),
             Box::new(service_target_port),
             Box::new(service_labels),
             Box::new(statefulset_grace_period_zero),
-        ]
+            Box::new(statefulset_misconfiguration),
+        ];
+
+        for rule in cfg.custom_rules.rules() {
+            lints.push(Box::new(linters::custom::CustomRuleLint::new(rule.clone())));
+        }
+
+        for script in cfg.scripts.lints() {
+            lints.push(Box::new(script));
+        }
+
+        for plugin in cfg.plugins.lints() {
+            lints.push(Box::new(plugin));
+        }
+
+        lints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LintCollection, LintSpecLoader};
+    use crate::config::Config;
+
+    #[test]
+    fn every_lint_has_a_spec() {
+        let specs = LintSpecLoader::new().unwrap();
+
+        for lint in LintCollection::all(&Config::default()) {
+            assert!(specs.get(lint.name()).is_some(), "{}", lint.name());
+        }
     }
 }
@@ -0,0 +1,371 @@
+use crate::linters::evaluator::Context;
+use crate::linters::{KubeObjectType, Lint, LintSpec};
+use crate::reporting::Finding;
+use anyhow::{anyhow, Context as _, Result};
+use log::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use wasmi::core::Trap;
+use wasmi::{
+    AsContext, Caller, Engine, Extern, Instance, Linker, Memory, Module, Store, StoreLimits,
+    StoreLimitsBuilder,
+};
+
+/// Version of the ABI implemented by the host. Plugins exporting a different version are rejected.
+const ABI_VERSION: i32 = 1;
+
+/// Lints compiled to WebAssembly and loaded from the `*.wasm` files of a directory. Plugins run
+/// sandboxed: they can only access their own memory and the functions exposed by the host.
+///
+/// Plugins have to export:
+///
+/// - `memory`: the linear memory used to exchange data with the host.
+/// - `korrecte_abi_version() -> i32`: version of the ABI implemented by the plugin.
+/// - `korrecte_alloc(len: i32) -> i32`: reserves `len` bytes and returns its offset.
+/// - `korrecte_spec() -> i64`: JSON encoded `LintSpec` of the plugin, packed as
+///   `offset << 32 | len`.
+/// - `korrecte_lint(offset: i32, len: i32)`: lints the JSON encoded object.
+///
+/// And can import `korrecte.report(offset: i32, len: i32)` to emit a finding for the current
+/// object, with a JSON encoded map of strings as metadata.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub(crate) struct Config {
+    /// Directory containing the plugins
+    #[serde(default)]
+    directory: Option<String>,
+    /// Maximum amount of fuel (roughly, instructions) a plugin can consume on each object
+    #[serde(default = "default_max_fuel")]
+    max_fuel: u64,
+    /// Maximum size, in bytes, of the memory of a plugin
+    #[serde(default = "default_max_memory")]
+    max_memory: usize,
+    /// Loaded plugins. It is filled when the configuration is loaded.
+    #[serde(skip)]
+    plugins: Vec<Plugin>,
+}
+
+impl Config {
+    /// Compiles all the plugins found on the configured directory
+    pub(crate) fn load_files(&mut self) -> Result<()> {
+        let directory = match self.directory {
+            Some(ref directory) => directory.clone(),
+            None => return Ok(()),
+        };
+
+        let mut paths = fs::read_dir(&directory)
+            .with_context(|| format!("Could not read plugins directory {}", directory))?
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| path.extension() == Some(OsStr::new("wasm")))
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        let engine = engine();
+        for path in paths {
+            let plugin = Plugin::load(&engine, &path, self.limits())
+                .with_context(|| format!("Could not load plugin {}", path.display()))?;
+            self.plugins.push(plugin);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn plugins(&self) -> &[Plugin] {
+        &self.plugins
+    }
+
+    pub(crate) fn lints(&self) -> Vec<PluginLint> {
+        self.plugins
+            .iter()
+            .map(|plugin| PluginLint {
+                plugin: plugin.clone(),
+                limits: self.limits(),
+            })
+            .collect()
+    }
+
+    fn limits(&self) -> Limits {
+        Limits {
+            fuel: self.max_fuel,
+            memory: self.max_memory,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            directory: None,
+            max_fuel: default_max_fuel(),
+            max_memory: default_max_memory(),
+            plugins: Vec::new(),
+        }
+    }
+}
+
+fn default_max_fuel() -> u64 {
+    10_000_000
+}
+
+fn default_max_memory() -> usize {
+    64 * 1024 * 1024
+}
+
+fn engine() -> Engine {
+    let mut config = wasmi::Config::default();
+    config.consume_fuel(true);
+
+    Engine::new(&config)
+}
+
+#[derive(Clone, Copy)]
+struct Limits {
+    fuel: u64,
+    memory: usize,
+}
+
+#[derive(Clone)]
+pub(crate) struct Plugin {
+    spec: LintSpec,
+    module: Arc<Module>,
+}
+
+impl fmt::Debug for Plugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Plugin").field("spec", &self.spec).finish()
+    }
+}
+
+impl Plugin {
+    fn load(engine: &Engine, path: &Path, limits: Limits) -> Result<Self> {
+        let bytes = fs::read(path)?;
+
+        Self::new(engine, &bytes, limits)
+    }
+
+    fn new(engine: &Engine, bytes: &[u8], limits: Limits) -> Result<Self> {
+        let module = Module::new(engine, bytes)?;
+        let (mut store, instance) = instantiate(&module, limits)?;
+
+        let packed = instance
+            .get_typed_func::<(), i64>(&store, "korrecte_spec")?
+            .call(&mut store, ())?;
+        let spec = read(&memory(&instance, &store)?, &store, unpack(packed))?;
+        let spec: LintSpec = serde_json::from_slice(spec).context("Invalid lint spec")?;
+
+        Ok(Plugin {
+            spec,
+            module: Arc::new(module),
+        })
+    }
+
+    pub fn spec(&self) -> &LintSpec {
+        &self.spec
+    }
+}
+
+/// State of the store of each plugin instance
+struct State {
+    limits: StoreLimits,
+    reported: Vec<HashMap<String, String>>,
+}
+
+/// Creates a new instance of the plugin and checks that it implements the expected ABI
+fn instantiate(module: &Module, limits: Limits) -> Result<(Store<State>, Instance)> {
+    let engine = module.engine();
+    let state = State {
+        limits: StoreLimitsBuilder::new().memory_size(limits.memory).build(),
+        reported: Vec::new(),
+    };
+
+    let mut store = Store::new(engine, state);
+    store.limiter(|state| &mut state.limits);
+    store.add_fuel(limits.fuel).map_err(wasmi::Error::from)?;
+
+    let mut linker = <Linker<State>>::new(engine);
+    linker.func_wrap("korrecte", "report", report)?;
+    let instance = linker.instantiate(&mut store, module)?.start(&mut store)?;
+
+    let version = instance
+        .get_typed_func::<(), i32>(&store, "korrecte_abi_version")?
+        .call(&mut store, ())?;
+    if version != ABI_VERSION {
+        return Err(anyhow!(
+            "Unsupported ABI version {}. Expected version {}",
+            version,
+            ABI_VERSION
+        ));
+    }
+
+    Ok((store, instance))
+}
+
+/// Host function exposed to the plugins as `korrecte.report`
+fn report(mut caller: Caller<'_, State>, offset: i32, len: i32) -> Result<(), Trap> {
+    let memory = caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| Trap::new("Missing memory export"))?;
+    let metadata = read(&memory, &caller, (offset as u32, len as u32))
+        .and_then(|bytes| Ok(serde_json::from_slice(bytes)?))
+        .map_err(|e| Trap::new(format!("Invalid finding: {}", e)))?;
+
+    caller.data_mut().reported.push(metadata);
+
+    Ok(())
+}
+
+fn memory(instance: &Instance, store: impl AsContext) -> Result<Memory> {
+    instance
+        .get_memory(store, "memory")
+        .ok_or_else(|| anyhow!("Missing memory export"))
+}
+
+fn read<'a>(
+    memory: &Memory,
+    store: &'a impl AsContext<UserState = State>,
+    (offset, len): (u32, u32),
+) -> Result<&'a [u8]> {
+    let start = offset as usize;
+    let end = start.saturating_add(len as usize);
+
+    memory
+        .data(store)
+        .get(start..end)
+        .ok_or_else(|| anyhow!("Out of bounds memory access"))
+}
+
+fn unpack(packed: i64) -> (u32, u32) {
+    ((packed >> 32) as u32, packed as u32)
+}
+
+pub(crate) struct PluginLint {
+    plugin: Plugin,
+    limits: Limits,
+}
+
+impl PluginLint {
+    fn run(&self, object: &KubeObjectType) -> Result<Vec<HashMap<String, String>>> {
+        let json = serde_json::to_vec(&object.to_json()?)?;
+        let (mut store, instance) = instantiate(&self.plugin.module, self.limits)?;
+
+        let offset = instance
+            .get_typed_func::<i32, i32>(&store, "korrecte_alloc")?
+            .call(&mut store, json.len() as i32)?;
+        memory(&instance, &store)?
+            .write(&mut store, offset as u32 as usize, &json)
+            .map_err(wasmi::Error::from)?;
+        instance
+            .get_typed_func::<(i32, i32), ()>(&store, "korrecte_lint")?
+            .call(&mut store, (offset, json.len() as i32))?;
+
+        Ok(store.into_data().reported)
+    }
+}
+
+impl Lint for PluginLint {
+    fn name(&self) -> &str {
+        &self.plugin.spec.name
+    }
+
+    fn object(&self, object: &KubeObjectType, context: &Context) {
+        let reported = match self.run(object) {
+            Ok(reported) => reported,
+            Err(e) => {
+                let name = object
+                    .metadata()
+                    .and_then(|m| m.name.clone())
+                    .unwrap_or_default();
+                error!("Plugin {} failed on {}: {}", self.name(), name, e);
+                return;
+            }
+        };
+
+        for metadata in reported {
+            let finding =
+                Finding::new(self.name(), object.metadata().cloned()).with_metadata(metadata);
+            context.reporter.report(finding);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Config;
+    use crate::linters::LintSpecLoader;
+    use crate::tests::{analyze_file_cfg, filter_findings_by};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::Instant;
+
+    /// Compiles the given plugins to a new directory and returns its path
+    fn plugins_directory(name: &str, plugins: &[&str]) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("korrecte-plugins-{}", name));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+
+        for plugin in plugins {
+            let wasm = wat::parse_file(format!("../tests/plugins/{}.wat", plugin)).unwrap();
+            fs::write(directory.join(format!("{}.wasm", plugin)), wasm).unwrap();
+        }
+
+        directory
+    }
+
+    fn load(directory: &Path, limits: &str) -> anyhow::Result<crate::config::Config> {
+        let mut plugins: Config = toml::from_str(&format!(
+            "directory = {:?}\n{}",
+            directory.to_str().unwrap(),
+            limits
+        ))
+        .unwrap();
+        plugins.load_files()?;
+
+        Ok(crate::config::Config {
+            plugins,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn it_reports_findings_from_plugins() {
+        let directory = plugins_directory("audit", &["audit"]);
+        let config = load(&directory, "").unwrap();
+
+        let spec = LintSpecLoader::with_config(&config).unwrap();
+        assert_eq!(
+            "Reports every object",
+            spec.get("wasm_audit").unwrap().description
+        );
+
+        let findings = analyze_file_cfg(Path::new("../tests/plugins.yaml"), config);
+        let findings = filter_findings_by(findings, "wasm_audit");
+
+        assert_eq!(2, findings.len());
+        assert_eq!("web", findings[0].name());
+        assert_eq!("wasm_audit", findings[0].lint_metadata()["plugin"]);
+    }
+
+    #[test]
+    fn it_stops_plugins_exceeding_the_limits() {
+        let directory = plugins_directory("endless", &["endless"]);
+        let config = load(&directory, "max_fuel = 100000").unwrap();
+
+        let started = Instant::now();
+        let findings = analyze_file_cfg(Path::new("../tests/plugins.yaml"), config);
+
+        assert!(filter_findings_by(findings, "wasm_endless").is_empty());
+        assert!(started.elapsed().as_secs() < 10);
+    }
+
+    #[test]
+    fn it_rejects_plugins_with_unsupported_abi() {
+        let directory = plugins_directory("unsupported", &["unsupported_abi"]);
+
+        assert!(load(&directory, "").is_err());
+    }
+}
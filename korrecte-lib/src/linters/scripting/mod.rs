@@ -0,0 +1,355 @@
+use crate::kube::repository::ObjectRepository;
+use crate::linters::evaluator::Context;
+use crate::linters::{KubeObjectType, Lint, LintSpec};
+use crate::reporting::Finding;
+use anyhow::{anyhow, Context as _, Result};
+use log::*;
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Lints written as [Rhai](https://rhai.rs) scripts. Each script is executed once per object,
+/// which is available on the `object` variable as a map. Scripts can call the following functions:
+///
+/// - `report()` and `report(metadata)`: emits a finding for the current object, with the given
+///   map as metadata.
+/// - `objects(kind)` and `objects(kind, namespace)`: returns all the objects of the given kind.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub(crate) struct Config {
+    /// Maximum amount of operations a script can execute on each object
+    #[serde(default = "default_max_operations")]
+    max_operations: u64,
+    /// Maximum time, in milliseconds, a script can spend on each object
+    #[serde(default = "default_timeout_ms")]
+    timeout_ms: u64,
+    #[serde(default)]
+    lints: Vec<Script>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub(crate) struct Script {
+    #[serde(flatten)]
+    spec: LintSpec,
+    path: String,
+    /// Compiled script. It is filled when the configuration is loaded.
+    #[serde(skip)]
+    ast: Option<AST>,
+}
+
+impl Script {
+    pub fn spec(&self) -> &LintSpec {
+        &self.spec
+    }
+}
+
+impl Config {
+    /// Reads and compiles all the configured scripts
+    pub(crate) fn load_files(&mut self) -> Result<()> {
+        let engine = Engine::new();
+
+        for script in self.lints.iter_mut() {
+            let mut file = File::open(Path::new(&script.path))
+                .with_context(|| format!("Could not load script {}", script.path))?;
+            let mut buffer = String::new();
+            file.read_to_string(&mut buffer)?;
+
+            let ast = engine
+                .compile(&buffer)
+                .map_err(|e| anyhow!("Could not compile script {}: {}", script.path, e))?;
+            script.ast = Some(ast);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn scripts(&self) -> &[Script] {
+        &self.lints
+    }
+
+    pub(crate) fn lints(&self) -> Vec<ScriptLint> {
+        self.lints
+            .iter()
+            .filter_map(|script| {
+                let ast = script.ast.clone()?;
+                Some(ScriptLint::new(script.spec.name.clone(), ast, self))
+            })
+            .collect()
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            max_operations: default_max_operations(),
+            timeout_ms: default_timeout_ms(),
+            lints: Vec::new(),
+        }
+    }
+}
+
+fn default_max_operations() -> u64 {
+    1_000_000
+}
+
+fn default_timeout_ms() -> u64 {
+    1000
+}
+
+/// Object of the repository, as seen by the scripts
+#[derive(Clone)]
+struct ScriptObject {
+    kind: &'static str,
+    namespace: Option<String>,
+    value: Dynamic,
+}
+
+/// State shared between the lint and the functions registered on the engine
+#[derive(Default)]
+struct State {
+    started: Option<Instant>,
+    reported: Vec<Map>,
+    /// Identity of the repository whose objects have been converted
+    repository: usize,
+    objects: Arc<Vec<ScriptObject>>,
+}
+
+pub(crate) struct ScriptLint {
+    name: String,
+    ast: AST,
+    engine: Engine,
+    state: Arc<Mutex<State>>,
+}
+
+impl ScriptLint {
+    fn new(name: String, ast: AST, config: &Config) -> Self {
+        let state = Arc::new(Mutex::new(State::default()));
+        let mut engine = Engine::new();
+        engine.set_max_operations(config.max_operations);
+
+        let timeout = Duration::from_millis(config.timeout_ms);
+        let progress_state = state.clone();
+        engine.on_progress(move |operations| {
+            // Checking the clock on every operation would slow down the scripts too much
+            if operations % 1024 != 0 {
+                return None;
+            }
+
+            let started = progress_state.lock().ok()?.started?;
+            if started.elapsed() > timeout {
+                Some(Dynamic::from("timeout"))
+            } else {
+                None
+            }
+        });
+
+        let report_state = state.clone();
+        engine.register_fn("report", move || {
+            if let Ok(mut state) = report_state.lock() {
+                state.reported.push(Map::new());
+            }
+        });
+        let report_state = state.clone();
+        engine.register_fn("report", move |metadata: Map| {
+            if let Ok(mut state) = report_state.lock() {
+                state.reported.push(metadata);
+            }
+        });
+
+        let objects_state = state.clone();
+        engine.register_fn("objects", move |kind: &str| -> Array {
+            Self::objects(&objects_state, kind, None)
+        });
+        let objects_state = state.clone();
+        engine.register_fn("objects", move |kind: &str, namespace: &str| -> Array {
+            Self::objects(&objects_state, kind, Some(namespace))
+        });
+
+        ScriptLint {
+            name,
+            ast,
+            engine,
+            state,
+        }
+    }
+
+    fn objects(state: &Mutex<State>, kind: &str, namespace: Option<&str>) -> Array {
+        let objects = match state.lock() {
+            Ok(state) => state.objects.clone(),
+            Err(_) => return Array::new(),
+        };
+
+        objects
+            .iter()
+            .filter(|o| o.kind == kind)
+            .filter(|o| namespace.is_none() || o.namespace.as_deref() == namespace)
+            .map(|o| o.value.clone())
+            .collect()
+    }
+
+    /// Converts all the objects of the repository to values usable by the scripts. Conversion is
+    /// only done once for each repository.
+    fn load_repository(&self, state: &mut State, repository: &dyn ObjectRepository) {
+        let identity = repository as *const dyn ObjectRepository as *const () as usize;
+        if state.repository == identity {
+            return;
+        }
+
+        let objects = repository
+            .iter()
+            .filter_map(|object| {
+                Some(ScriptObject {
+                    kind: object.kind(),
+                    namespace: object.metadata().and_then(|m| m.namespace.clone()),
+                    value: to_dynamic(object)?,
+                })
+            })
+            .collect();
+
+        state.repository = identity;
+        state.objects = Arc::new(objects);
+    }
+
+    fn run(&self, object: &KubeObjectType, context: &Context) -> Result<Vec<Map>> {
+        let value = to_dynamic(object).ok_or_else(|| anyhow!("Could not convert object"))?;
+
+        {
+            let mut state = self.state.lock().map_err(|_| anyhow!("Poisoned state"))?;
+            self.load_repository(&mut state, context.repository);
+            state.reported.clear();
+            state.started = Some(Instant::now());
+        }
+
+        let mut scope = Scope::new();
+        scope.push("object", value);
+        let result = self.engine.run_ast_with_scope(&mut scope, &self.ast);
+
+        let mut state = self.state.lock().map_err(|_| anyhow!("Poisoned state"))?;
+        state.started = None;
+        result.map_err(|e| anyhow!("{}", e))?;
+
+        Ok(std::mem::take(&mut state.reported))
+    }
+}
+
+fn to_dynamic(object: &KubeObjectType) -> Option<Dynamic> {
+    let json = object.to_json().ok()?;
+
+    rhai::serde::to_dynamic(json).ok()
+}
+
+impl Lint for ScriptLint {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn object(&self, object: &KubeObjectType, context: &Context) {
+        let reported = match self.run(object, context) {
+            Ok(reported) => reported,
+            Err(e) => {
+                let name = object
+                    .metadata()
+                    .and_then(|m| m.name.clone())
+                    .unwrap_or_default();
+                error!("Script {} failed on {}: {}", self.name, name, e);
+                return;
+            }
+        };
+
+        for metadata in reported {
+            let finding = metadata.into_iter().fold(
+                Finding::new(&self.name, object.metadata().cloned()),
+                |finding, (key, value)| finding.add_metadata(key, value),
+            );
+
+            context.reporter.report(finding);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Config;
+    use crate::linters::LintSpecLoader;
+    use crate::tests::{analyze_file_cfg, filter_findings_by};
+    use std::path::Path;
+    use std::time::Instant;
+
+    fn config(scripts: &str) -> crate::config::Config {
+        let mut scripts: Config = toml::from_str(scripts).unwrap();
+        scripts.load_files().unwrap();
+
+        crate::config::Config {
+            scripts,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_reports_findings_from_scripts() {
+        let config = config(
+            r#"
+[[lints]]
+name = "service_without_pods"
+group = "Configuration"
+severity = "error"
+description = "Services selecting no pods"
+path = "../tests/scripts/service_without_pods.rhai"
+"#,
+        );
+
+        let spec = LintSpecLoader::with_config(&config).unwrap();
+        assert_eq!(
+            "Services selecting no pods",
+            spec.get("service_without_pods").unwrap().description
+        );
+
+        let findings = analyze_file_cfg(Path::new("../tests/scripts.yaml"), config);
+        let findings = filter_findings_by(findings, "service_without_pods");
+
+        assert_eq!(1, findings.len());
+        assert_eq!("orphan", findings[0].name());
+        assert_eq!("ClusterIP", findings[0].lint_metadata()["type"]);
+        assert_eq!("2", findings[0].lint_metadata()["pods"]);
+    }
+
+    #[test]
+    fn it_stops_scripts_exceeding_the_limits() {
+        let endless = r#"
+[[lints]]
+name = "endless"
+group = "Audit"
+description = "Never finishes"
+path = "../tests/scripts/endless.rhai"
+"#;
+        let operations_limited = format!("max_operations = 10000\ntimeout_ms = 60000\n{}", endless);
+        let time_limited = format!("max_operations = 0\ntimeout_ms = 50\n{}", endless);
+
+        for limits in [operations_limited, time_limited].iter() {
+            let started = Instant::now();
+            let findings = analyze_file_cfg(Path::new("../tests/scripts.yaml"), config(limits));
+
+            assert!(filter_findings_by(findings, "endless").is_empty());
+            assert!(started.elapsed().as_secs() < 10);
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_scripts() {
+        let mut scripts: Config = toml::from_str(
+            r#"
+[[lints]]
+name = "invalid"
+group = "Audit"
+description = "Does not compile"
+path = "../tests/scripts/invalid.rhai"
+"#,
+        )
+        .unwrap();
+
+        assert!(scripts.load_files().is_err());
+    }
+}
@@ -0,0 +1,43 @@
+//! Helpers to build the JSON patches suggested by the lints to fix their findings
+
+use json_patch::{AddOperation, PatchOperation, RemoveOperation, ReplaceOperation};
+use serde_json::Value;
+
+/// Builds a JSON pointer from its unescaped segments
+pub(crate) fn pointer<'a, I: IntoIterator<Item = &'a str>>(prefix: &str, segments: I) -> String {
+    segments
+        .into_iter()
+        .fold(prefix.to_string(), |pointer, segment| {
+            format!(
+                "{}/{}",
+                pointer,
+                segment.replace('~', "~0").replace('/', "~1")
+            )
+        })
+}
+
+pub(crate) fn add(path: String, value: Value) -> PatchOperation {
+    PatchOperation::Add(AddOperation { path, value })
+}
+
+pub(crate) fn remove(path: String) -> PatchOperation {
+    PatchOperation::Remove(RemoveOperation { path })
+}
+
+pub(crate) fn replace(path: String, value: Value) -> PatchOperation {
+    PatchOperation::Replace(ReplaceOperation { path, value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pointer;
+
+    #[test]
+    fn it_escapes_pointer_segments() {
+        assert_eq!(
+            "/metadata/labels/app.kubernetes.io~1name",
+            pointer("/metadata", vec!["labels", "app.kubernetes.io/name"])
+        );
+        assert_eq!("/spec/a~0b", pointer("/spec", vec!["a~b"]));
+    }
+}
@@ -1,3 +1,7 @@
+pub(crate) mod fix;
+
+use crate::linters::Severity;
+pub use json_patch::Patch;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 use serde::Serialize;
 use std::cell::RefCell;
@@ -17,6 +21,15 @@ pub struct Finding {
     namespace: Option<String>,
     // TODO: Think about a better data structure
     lint_metadata: HashMap<String, String>,
+    /// Suggested fix, as a JSON patch to be applied on the object
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fix: Option<Patch>,
+    /// Whether the fix may break the object or needs to be reviewed, like placeholder values
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    unsafe_fix: bool,
+    /// Severity set by the configuration overrides, replacing the one of the lint spec
+    #[serde(skip_serializing_if = "Option::is_none")]
+    severity: Option<Severity>,
 }
 
 impl Finding {
@@ -28,6 +41,9 @@ impl Finding {
             name: metadata.name.unwrap_or_default(),
             namespace: metadata.namespace.clone(),
             lint_metadata: HashMap::new(),
+            fix: None,
+            unsafe_fix: false,
+            severity: None,
         }
     }
 
@@ -42,6 +58,24 @@ impl Finding {
         self
     }
 
+    pub fn with_fix(mut self, fix: Patch) -> Self {
+        self.fix = Some(fix);
+        self.unsafe_fix = false;
+        self
+    }
+
+    /// Suggests a fix which is only applied when explicitly requested
+    pub fn with_unsafe_fix(mut self, fix: Patch) -> Self {
+        self.fix = Some(fix);
+        self.unsafe_fix = true;
+        self
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = Some(severity);
+        self
+    }
+
     pub fn lint_name(&self) -> &String {
         &self.lint_name
     }
@@ -56,6 +90,18 @@ impl Finding {
         &self.name
     }
 
+    pub fn fix(&self) -> Option<&Patch> {
+        self.fix.as_ref()
+    }
+
+    pub fn is_fix_unsafe(&self) -> bool {
+        self.unsafe_fix
+    }
+
+    pub fn severity(&self) -> Option<&Severity> {
+        self.severity.as_ref()
+    }
+
     #[allow(unused)]
     pub fn namespace(&self) -> &Option<String> {
         &self.namespace
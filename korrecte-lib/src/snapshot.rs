@@ -0,0 +1,288 @@
+use crate::kube::repository::api_async::ApiObjectRepository;
+use crate::kube::repository::file::KubeObjectLoader;
+use crate::linters::KubeObjectType;
+use anyhow::{anyhow, Context, Result};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+use k8s_openapi::apimachinery::pkg::version::Info;
+use k8s_openapi::chrono::Utc;
+use log::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Magic number which starts every zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+const METADATA_FILE: &str = "metadata.json";
+const OBJECTS_DIRECTORY: &str = "objects/";
+const LAST_APPLIED_ANNOTATION: &str = "kubectl.kubernetes.io/last-applied-configuration";
+
+/// Objects of a cluster captured at some point, which can be linted later on without access to
+/// the cluster.
+///
+/// Snapshots are stored as zstd compressed tarballs containing the metadata of the capture and
+/// a multi-document YAML file for each kind of object. The values of the secrets are never
+/// stored, so snapshots can be shared: only their keys are kept.
+pub struct Snapshot {
+    pub metadata: SnapshotMetadata,
+    pub objects: Vec<KubeObjectType>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SnapshotMetadata {
+    /// Version of korrecte which captured the snapshot
+    pub korrecte_version: String,
+    pub captured_at: Time,
+    /// Version of the API server, if it could be retrieved
+    pub server_version: Option<Info>,
+    /// Amount of objects on the snapshot
+    pub objects: usize,
+    /// Kinds of objects which could not be loaded from the cluster, so they are missing
+    #[serde(default)]
+    pub failed_kinds: Vec<String>,
+}
+
+impl Snapshot {
+    pub fn new(objects: Vec<KubeObjectType>, server_version: Option<Info>) -> Snapshot {
+        Snapshot {
+            metadata: SnapshotMetadata {
+                korrecte_version: env!("CARGO_PKG_VERSION").to_string(),
+                captured_at: Time(Utc::now()),
+                server_version,
+                objects: objects.len(),
+                failed_kinds: Vec::new(),
+            },
+            objects,
+        }
+    }
+
+    /// Captures all the objects of the cluster of the current kubernetes context
+    pub fn capture() -> Result<Snapshot> {
+        let api = ApiObjectRepository::new()?;
+        let mut rt = tokio::runtime::Runtime::new()?;
+
+        let server_version = match rt.block_on(api.server_version()) {
+            Ok(version) => Some(version),
+            Err(e) => {
+                warn!("Could not retrieve the version of the server: {}", e);
+                None
+            }
+        };
+        let loaded = rt.block_on(api.load_all_objects());
+
+        let mut snapshot = Snapshot::new(loaded.objects, server_version);
+        snapshot.metadata.failed_kinds = loaded.failed_kinds;
+        Ok(snapshot)
+    }
+
+    pub fn is_snapshot(path: &Path) -> bool {
+        let mut magic = [0; 4];
+
+        path.is_file()
+            && File::open(path)
+                .and_then(|mut file| file.read_exact(&mut magic))
+                .is_ok()
+            && magic == ZSTD_MAGIC
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let file = File::create(path)
+            .with_context(|| format!("Could not create snapshot {}", path.display()))?;
+        let mut builder = tar::Builder::new(zstd::Encoder::new(file, 0)?);
+        let mtime = self.metadata.captured_at.0.timestamp() as u64;
+
+        let metadata = serde_json::to_vec_pretty(&self.metadata)?;
+        append(&mut builder, METADATA_FILE, &metadata, mtime)?;
+        for (name, manifests) in manifests(&self.objects)? {
+            append(&mut builder, &name, manifests.as_bytes(), mtime)?;
+        }
+
+        builder.into_inner()?.finish()?.flush()?;
+
+        Ok(())
+    }
+
+    pub fn read(path: &Path) -> Result<Snapshot> {
+        let file = File::open(path)
+            .with_context(|| format!("Could not open snapshot {}", path.display()))?;
+        let mut archive = tar::Archive::new(zstd::Decoder::new(file)?);
+
+        let mut metadata = None;
+        let mut objects = Vec::new();
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let name = entry.path()?.to_string_lossy().to_string();
+            let mut content = String::new();
+            entry.read_to_string(&mut content)?;
+
+            if name == METADATA_FILE {
+                metadata = Some(serde_json::from_str::<SnapshotMetadata>(&content)?);
+            } else if name.starts_with(OBJECTS_DIRECTORY) {
+                for object in KubeObjectLoader::read(&content)? {
+                    match object {
+                        Ok(object) => objects.push(object),
+                        Err(e) => warn!("Could not load object from {}: {}", name, e),
+                    }
+                }
+            }
+        }
+
+        let metadata = metadata.ok_or_else(|| {
+            anyhow!(
+                "Snapshot {} does not contain {}",
+                path.display(),
+                METADATA_FILE
+            )
+        })?;
+
+        Ok(Snapshot { metadata, objects })
+    }
+}
+
+/// Multi-document YAML manifests of the objects, grouped by their api version and kind
+fn manifests(objects: &[KubeObjectType]) -> Result<BTreeMap<String, String>> {
+    let mut manifests = BTreeMap::new();
+
+    for object in objects {
+        let mut json = object.to_json()?;
+        redact_secret(&mut json);
+
+        let api_version = json["apiVersion"].as_str().unwrap_or_default();
+        let name = format!(
+            "{}{}/{}.yaml",
+            OBJECTS_DIRECTORY,
+            api_version,
+            object.kind()
+        );
+        let manifest: &mut String = manifests.entry(name).or_default();
+        manifest.push_str(&serde_yaml::to_string(&json)?);
+        manifest.push('\n');
+    }
+
+    Ok(manifests)
+}
+
+/// Replaces the values of a secret by empty strings, keeping its keys. The last applied
+/// configuration and the managed fields are removed too, as they may contain the values.
+fn redact_secret(object: &mut Value) {
+    if object["kind"] != "Secret" {
+        return;
+    }
+
+    for field in &["data", "stringData"] {
+        if let Some(Value::Object(values)) = object.get_mut(*field) {
+            for value in values.values_mut() {
+                *value = Value::String(String::new());
+            }
+        }
+    }
+
+    if let Some(Value::Object(metadata)) = object.get_mut("metadata") {
+        metadata.remove("managedFields");
+        if let Some(Value::Object(annotations)) = metadata.get_mut("annotations") {
+            annotations.remove(LAST_APPLIED_ANNOTATION);
+        }
+    }
+}
+
+fn append<W: Write>(
+    builder: &mut tar::Builder<W>,
+    name: &str,
+    content: &[u8],
+    mtime: u64,
+) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(mtime);
+    header.set_cksum();
+    builder.append_data(&mut header, name, content)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Snapshot;
+    use crate::kube::repository::file::{FileObjectRepository, KubeObjectLoader};
+    use crate::kube::repository::ObjectRepository;
+    use crate::linters::KubeObjectType;
+    use k8s_openapi::apimachinery::pkg::version::Info;
+    use std::fs::{self, File};
+    use std::io::Read;
+    use std::path::Path;
+
+    #[test]
+    fn it_replays_snapshots_as_files() {
+        let objects: Vec<KubeObjectType> =
+            KubeObjectLoader::read_file(Path::new("../tests/snapshot.yaml"))
+                .unwrap()
+                .into_iter()
+                .map(Result::unwrap)
+                .collect();
+        let server_version = Info {
+            git_version: "v1.16.3".to_string(),
+            ..Default::default()
+        };
+        let path = std::env::temp_dir().join(format!("korrecte-{}.tar.zst", std::process::id()));
+
+        let mut snapshot = Snapshot::new(objects, Some(server_version));
+        snapshot.metadata.failed_kinds = vec!["RbacV1ClusterRole".to_string()];
+        snapshot.write(&path).unwrap();
+        assert!(Snapshot::is_snapshot(&path));
+        assert!(!Snapshot::is_snapshot(Path::new("../tests/snapshot.yaml")));
+
+        let snapshot = Snapshot::read(&path).unwrap();
+        let repository = FileObjectRepository::new(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(3, snapshot.metadata.objects);
+        assert_eq!(vec!["RbacV1ClusterRole"], snapshot.metadata.failed_kinds);
+        assert_eq!(
+            "v1.16.3",
+            snapshot.metadata.server_version.unwrap().git_version
+        );
+        assert_eq!(3, repository.iter().count());
+
+        let secret = repository
+            .iter()
+            .find_map(|object| match object {
+                KubeObjectType::CoreV1Secret(secret) => Some(secret),
+                _ => None,
+            })
+            .unwrap();
+        let data = secret.data.as_ref().unwrap();
+        assert!(data.contains_key("password"));
+        assert!(data.values().all(|value| value.0.is_empty()));
+    }
+
+    #[test]
+    fn it_does_not_store_secret_values() {
+        let objects: Vec<KubeObjectType> =
+            KubeObjectLoader::read_file(Path::new("../tests/snapshot.yaml"))
+                .unwrap()
+                .into_iter()
+                .map(Result::unwrap)
+                .collect();
+        let path =
+            std::env::temp_dir().join(format!("korrecte-secrets-{}.tar.zst", std::process::id()));
+        Snapshot::new(objects, None).write(&path).unwrap();
+
+        let mut content = String::new();
+        let file = File::open(&path).unwrap();
+        let mut archive = tar::Archive::new(zstd::Decoder::new(file).unwrap());
+        for entry in archive.entries().unwrap() {
+            entry.unwrap().read_to_string(&mut content).unwrap();
+        }
+        fs::remove_file(&path).unwrap();
+
+        assert!(content.contains("api-credentials"));
+        assert!(content.contains("owner: platform"));
+        assert!(!content.contains("c3VwZXItc2VjcmV0"));
+        assert!(!content.contains("t0k3n-v4lu3"));
+        assert!(!content.contains("last-applied-configuration"));
+        assert!(!content.contains("managedFields"));
+    }
+}
@@ -18,7 +18,7 @@ pub fn analyze_file_cfg(path: &Path, config: Config) -> Vec<Finding> {
 
     let ll = LintCollection::all(&config);
 
-    let evaluator = SingleEvaluator;
+    let evaluator = SingleEvaluator::new(&config);
     evaluator.evaluate(&context, &ll);
 
     reporter.findings()
@@ -51,6 +51,19 @@ pub(crate) fn pod_spec_visit<V: PodSpecVisitor>(object: &KubeObjectType, visitor
     }
 }
 
+/// JSON pointer to the pod template visited by `pod_spec_visit`, which contains the `metadata` and
+/// `spec` of the pods. Bare pods are their own template, so its pointer is empty.
+pub(crate) fn pod_template_pointer(object: &KubeObjectType) -> Option<&'static str> {
+    match object {
+        KubeObjectType::CoreV1Pod(_) => Some(""),
+        KubeObjectType::AppsV1Deployment(_)
+        | KubeObjectType::AppsV1DaemonSet(_)
+        | KubeObjectType::AppsV1ReplicaSet(_)
+        | KubeObjectType::AppsV1StatefulSet(_) => Some("/spec/template"),
+        _ => None,
+    }
+}
+
 fn visit_pod_template<V: PodSpecVisitor>(
     template: Option<&PodTemplateSpec>,
     object_meta: Option<&ObjectMeta>,
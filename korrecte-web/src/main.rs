@@ -4,6 +4,7 @@ use gotham::router::builder::*;
 use gotham::router::Router;
 use gotham::state::State;
 use hyper::{Body, Response, StatusCode};
+use korrecte::config::ConfigLayers;
 use korrecte::executor::{ExecutionContextBuilder, ExecutionMode, Executor};
 use korrecte::reporting::{Finding, Reporter};
 use std::path::Path;
@@ -41,9 +42,11 @@ fn inner_evaluator_handler() -> Result<String, anyhow::Error> {
 }
 
 fn analyze_cluster() -> Result<Vec<Finding>, anyhow::Error> {
+    let layers = ConfigLayers::load(None, Path::new("."))
+        .map_err(|e| anyhow!("Could not load configuration: {}", e))?;
     let context = ExecutionContextBuilder::default()
-        .configuration_from_path(Path::new("korrecte.toml"))
-        .map_err(|e| anyhow!("Could not find configuration file: {:?}", e))?
+        .configuration_from_layers(&layers)
+        .map_err(|e| anyhow!("Could not load configuration: {}", e))?
         .execution_mode(ExecutionMode::Api)
         .build();
 
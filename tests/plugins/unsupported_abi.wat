@@ -0,0 +1,30 @@
+;; Plugin used on the tests of the WebAssembly plugin host
+(module
+  (import "korrecte" "report" (func $report (param i32 i32)))
+  (memory (export "memory") 1)
+  (global $heap (mut i32) (i32.const 1024))
+  (data (i32.const 0) "{\"group\":\"Audit\",\"name\":\"wasm_unsupported\",\"severity\":\"info\",\"description\":\"Reports every object\",\"references\":[]}")
+  (data (i32.const 512) "{\"plugin\":\"wasm_audit\"}")
+
+  (func (export "korrecte_abi_version") (result i32)
+    i32.const 2)
+
+  ;; Bump allocator: memory is never freed, as each object is linted on a new instance
+  (func (export "korrecte_alloc") (param $len i32) (result i32)
+    (local $offset i32)
+    global.get $heap
+    local.set $offset
+    (memory.grow
+      (i32.add (i32.shr_u (local.get $len) (i32.const 16)) (i32.const 1)))
+    drop
+    (global.set $heap (i32.add (global.get $heap) (local.get $len)))
+    local.get $offset)
+
+  ;; Spec is stored at offset 0 and is 114 bytes long
+  (func (export "korrecte_spec") (result i64)
+    i64.const 114)
+
+  (func (export "korrecte_lint") (param $offset i32) (param $len i32)
+    ;; Metadata is stored at offset 512 and is 23 bytes long
+    (call $report (i32.const 512) (i32.const 23)))
+)